@@ -0,0 +1,346 @@
+//!
+//! This module provides adapters that change how a graph is viewed, without copying it.
+//!
+//! * [adapters::Reversed] reverses the direction of all edges.
+//! * [adapters::AsUndirected] ignores the direction of all edges.
+//!
+//! Both wrap a reference to any [graph::Graph] implementation, and implement [graph::Graph] themselves.
+//! Node and edge references stay the same, so results found on an adapter
+//! can be used directly with the wrapped graph. For example, an adapter can be passed as
+//! base graph to [pattern_matching::solve_vf].
+
+use std::marker::PhantomData;
+
+use crate::graph::{self};
+
+/// View of a graph with all edges reversed.
+///
+/// Incoming edges of the wrapped graph are reported as outgoing edges and vice versa,
+/// and [graph::Graph::adjacent_nodes] returns the destination of a directed edge first.
+/// Undirected graphs and edges are not affected.
+pub struct Reversed<'g, NodeWeight, EdgeWeight, Graph>
+where
+    Graph: graph::Graph<NodeWeight, EdgeWeight>,
+{
+    graph: &'g Graph,
+    weights: PhantomData<fn() -> (NodeWeight, EdgeWeight)>,
+}
+
+impl<'g, NodeWeight, EdgeWeight, Graph> Reversed<'g, NodeWeight, EdgeWeight, Graph>
+where
+    Graph: graph::Graph<NodeWeight, EdgeWeight>,
+{
+    /// Creates a reversed view of `graph`.
+    pub fn new(graph: &'g Graph) -> Self {
+        Self {
+            graph,
+            weights: PhantomData,
+        }
+    }
+}
+
+impl<'g, NodeWeight, EdgeWeight, Graph> graph::Graph<NodeWeight, EdgeWeight>
+    for Reversed<'g, NodeWeight, EdgeWeight, Graph>
+where
+    Graph: graph::Graph<NodeWeight, EdgeWeight>,
+{
+    type NodeRef = Graph::NodeRef;
+
+    type EdgeRef = Graph::EdgeRef;
+
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+
+    fn is_directed_edge(&self, edge: Self::EdgeRef) -> bool {
+        self.graph.is_directed_edge(edge)
+    }
+
+    type AdjacentEdgesIterator<'a>
+        = Graph::AdjacentEdgesIterator<'a>
+    where
+        Self: 'a;
+
+    fn adjacent_edges(&self, node: Self::NodeRef) -> Self::AdjacentEdgesIterator<'_> {
+        self.graph.adjacent_edges(node)
+    }
+
+    type IncomingEdgesIterator<'a>
+        = Graph::OutgoingEdgesIterator<'a>
+    where
+        Self: 'a;
+
+    fn incoming_edges(&self, node: Self::NodeRef) -> Self::IncomingEdgesIterator<'_> {
+        self.graph.outgoing_edges(node)
+    }
+
+    type OutgoingEdgesIterator<'a>
+        = Graph::IncomingEdgesIterator<'a>
+    where
+        Self: 'a;
+
+    fn outgoing_edges(&self, node: Self::NodeRef) -> Self::OutgoingEdgesIterator<'_> {
+        self.graph.incoming_edges(node)
+    }
+
+    fn in_degree(&self, node: Self::NodeRef) -> usize {
+        self.graph.out_degree(node)
+    }
+
+    fn out_degree(&self, node: Self::NodeRef) -> usize {
+        self.graph.in_degree(node)
+    }
+
+    fn degree(&self, node: Self::NodeRef) -> usize {
+        self.graph.degree(node)
+    }
+
+    fn adjacent_nodes(&self, edge: Self::EdgeRef) -> (Self::NodeRef, Self::NodeRef) {
+        let (a, b) = self.graph.adjacent_nodes(edge);
+        if self.graph.is_directed_edge(edge) {
+            (b, a)
+        } else {
+            (a, b)
+        }
+    }
+
+    fn node_weight(&self, node: Self::NodeRef) -> &NodeWeight {
+        self.graph.node_weight(node)
+    }
+
+    fn edge_weight(&self, edge: Self::EdgeRef) -> &EdgeWeight {
+        self.graph.edge_weight(edge)
+    }
+
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        self.graph.contains_node(node)
+    }
+
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        self.graph.contains_edge(edge)
+    }
+
+    fn try_adjacent_nodes(&self, edge: Self::EdgeRef) -> Option<(Self::NodeRef, Self::NodeRef)> {
+        let (a, b) = self.graph.try_adjacent_nodes(edge)?;
+        if self.graph.is_directed_edge(edge) {
+            Some((b, a))
+        } else {
+            Some((a, b))
+        }
+    }
+
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        self.graph.try_node_weight(node)
+    }
+
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        self.graph.try_edge_weight(edge)
+    }
+
+    type NodeWeightsIterator<'a>
+        = Graph::NodeWeightsIterator<'a>
+    where
+        Self: 'a,
+        NodeWeight: 'a;
+
+    fn node_weights(&self) -> Self::NodeWeightsIterator<'_> {
+        self.graph.node_weights()
+    }
+
+    type EdgeWeightsIterator<'a>
+        = Graph::EdgeWeightsIterator<'a>
+    where
+        Self: 'a,
+        EdgeWeight: 'a;
+
+    fn edge_weights(&self) -> Self::EdgeWeightsIterator<'_> {
+        self.graph.edge_weights()
+    }
+
+    type NodesIterator<'a>
+        = Graph::NodesIterator<'a>
+    where
+        Self: 'a;
+
+    fn nodes(&self) -> Self::NodesIterator<'_> {
+        self.graph.nodes()
+    }
+
+    type EdgesIterator<'a>
+        = Graph::EdgesIterator<'a>
+    where
+        Self: 'a;
+
+    fn edges(&self) -> Self::EdgesIterator<'_> {
+        self.graph.edges()
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.graph.count_nodes()
+    }
+
+    fn count_edges(&self) -> usize {
+        self.graph.count_edges()
+    }
+}
+
+/// View of a graph with the direction of all edges ignored.
+///
+/// Every edge is reported as undirected, and as both incoming and outgoing edge of its nodes.
+pub struct AsUndirected<'g, NodeWeight, EdgeWeight, Graph>
+where
+    Graph: graph::Graph<NodeWeight, EdgeWeight>,
+{
+    graph: &'g Graph,
+    weights: PhantomData<fn() -> (NodeWeight, EdgeWeight)>,
+}
+
+impl<'g, NodeWeight, EdgeWeight, Graph> AsUndirected<'g, NodeWeight, EdgeWeight, Graph>
+where
+    Graph: graph::Graph<NodeWeight, EdgeWeight>,
+{
+    /// Creates an undirected view of `graph`.
+    pub fn new(graph: &'g Graph) -> Self {
+        Self {
+            graph,
+            weights: PhantomData,
+        }
+    }
+
+    /// Returns all edges of `node`, each of them once.
+    fn undirected_edges(&self, node: Graph::NodeRef) -> impl Iterator<Item = Graph::EdgeRef> + '_ {
+        // Outgoing edges already contain all undirected edges and self loops,
+        // so only the remaining directed incoming edges need to be added.
+        self.graph
+            .outgoing_edges(node)
+            .chain(self.graph.incoming_edges(node).filter(move |e| {
+                let (a, b) = self.graph.adjacent_nodes(*e);
+                self.graph.is_directed_edge(*e) && a != b
+            }))
+    }
+}
+
+impl<'g, NodeWeight, EdgeWeight, Graph> graph::Graph<NodeWeight, EdgeWeight>
+    for AsUndirected<'g, NodeWeight, EdgeWeight, Graph>
+where
+    Graph: graph::Graph<NodeWeight, EdgeWeight>,
+{
+    type NodeRef = Graph::NodeRef;
+
+    type EdgeRef = Graph::EdgeRef;
+
+    fn is_directed(&self) -> bool {
+        false
+    }
+
+    fn is_directed_edge(&self, edge: Self::EdgeRef) -> bool {
+        // Let the wrapped graph reject invalid references.
+        self.graph.is_directed_edge(edge);
+        false
+    }
+
+    type AdjacentEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn adjacent_edges(&self, node: Self::NodeRef) -> Self::AdjacentEdgesIterator<'_> {
+        self.undirected_edges(node)
+    }
+
+    type IncomingEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn incoming_edges(&self, node: Self::NodeRef) -> Self::IncomingEdgesIterator<'_> {
+        self.undirected_edges(node)
+    }
+
+    type OutgoingEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn outgoing_edges(&self, node: Self::NodeRef) -> Self::OutgoingEdgesIterator<'_> {
+        self.undirected_edges(node)
+    }
+
+    fn adjacent_nodes(&self, edge: Self::EdgeRef) -> (Self::NodeRef, Self::NodeRef) {
+        self.graph.adjacent_nodes(edge)
+    }
+
+    fn node_weight(&self, node: Self::NodeRef) -> &NodeWeight {
+        self.graph.node_weight(node)
+    }
+
+    fn edge_weight(&self, edge: Self::EdgeRef) -> &EdgeWeight {
+        self.graph.edge_weight(edge)
+    }
+
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        self.graph.contains_node(node)
+    }
+
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        self.graph.contains_edge(edge)
+    }
+
+    fn try_adjacent_nodes(&self, edge: Self::EdgeRef) -> Option<(Self::NodeRef, Self::NodeRef)> {
+        self.graph.try_adjacent_nodes(edge)
+    }
+
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        self.graph.try_node_weight(node)
+    }
+
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        self.graph.try_edge_weight(edge)
+    }
+
+    type NodeWeightsIterator<'a>
+        = Graph::NodeWeightsIterator<'a>
+    where
+        Self: 'a,
+        NodeWeight: 'a;
+
+    fn node_weights(&self) -> Self::NodeWeightsIterator<'_> {
+        self.graph.node_weights()
+    }
+
+    type EdgeWeightsIterator<'a>
+        = Graph::EdgeWeightsIterator<'a>
+    where
+        Self: 'a,
+        EdgeWeight: 'a;
+
+    fn edge_weights(&self) -> Self::EdgeWeightsIterator<'_> {
+        self.graph.edge_weights()
+    }
+
+    type NodesIterator<'a>
+        = Graph::NodesIterator<'a>
+    where
+        Self: 'a;
+
+    fn nodes(&self) -> Self::NodesIterator<'_> {
+        self.graph.nodes()
+    }
+
+    type EdgesIterator<'a>
+        = Graph::EdgesIterator<'a>
+    where
+        Self: 'a;
+
+    fn edges(&self) -> Self::EdgesIterator<'_> {
+        self.graph.edges()
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.graph.count_nodes()
+    }
+
+    fn count_edges(&self) -> usize {
+        self.graph.count_edges()
+    }
+}
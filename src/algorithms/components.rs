@@ -0,0 +1,183 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    vec,
+};
+
+use petgraph::graph::{DiGraph, NodeIndex};
+
+use crate::{
+    filter_map::FilterMap,
+    graph::{outgoing_nodes, Graph},
+};
+
+use super::{Bfs, Direction};
+
+/// View of a single component of a graph, as returned by the component algorithms.
+///
+/// Weights are references to the weights of the base graph.
+pub type ComponentView<'g, NodeWeight, EdgeWeight, G> =
+    FilterMap<'g, NodeWeight, EdgeWeight, &'g NodeWeight, &'g EdgeWeight, G>;
+
+/// Splits `g` into its weakly connected components, i.e. the maximal subgraphs in which
+/// all nodes are connected when ignoring the direction of edges.
+///
+/// Returns a view of `g` for each component, containing its nodes and all edges between them.
+pub fn weakly_connected_components<'g, NW, EW, G>(g: &'g G) -> Vec<ComponentView<'g, NW, EW, G>>
+where
+    G: Graph<NW, EW>,
+{
+    let mut visited = HashSet::new();
+    let mut components = vec![];
+    for root in g.nodes() {
+        if visited.contains(&root) {
+            continue;
+        }
+        let nodes: Vec<_> = Bfs::new(g, root, Direction::Both).collect();
+        visited.extend(nodes.iter().copied());
+        components.push(FilterMap::induced_subgraph(g, nodes));
+    }
+    components
+}
+
+/// State of Tarjan's algorithm for finding strongly connected components.
+struct Tarjan<NodeRef> {
+    /// Order in which nodes have been discovered.
+    index: HashMap<NodeRef, usize>,
+    /// Lowest index reachable from each node through its descendants and a single back edge.
+    lowlink: HashMap<NodeRef, usize>,
+    /// Visited nodes that haven't been assigned to a component yet.
+    stack: Vec<NodeRef>,
+    on_stack: HashSet<NodeRef>,
+    /// Emulates the recursion of the algorithm, storing the unexplored successors of each node.
+    call_stack: Vec<(NodeRef, vec::IntoIter<NodeRef>)>,
+    components: Vec<Vec<NodeRef>>,
+}
+
+impl<NodeRef: Copy + Eq + Hash> Tarjan<NodeRef> {
+    /// Discovers node `n`, and starts exploring its successors.
+    fn visit<G, NW, EW>(&mut self, g: &G, n: NodeRef)
+    where
+        G: Graph<NW, EW, NodeRef = NodeRef>,
+    {
+        self.index.insert(n, self.index.len());
+        self.lowlink.insert(n, self.index[&n]);
+        self.stack.push(n);
+        self.on_stack.insert(n);
+        let successors: Vec<_> = outgoing_nodes(g, n).collect();
+        self.call_stack.push((n, successors.into_iter()));
+    }
+
+    /// Runs the algorithm until all nodes reachable from `root` have been assigned to components.
+    fn run<G, NW, EW>(&mut self, g: &G, root: NodeRef)
+    where
+        G: Graph<NW, EW, NodeRef = NodeRef>,
+    {
+        self.visit(g, root);
+        while let Some((n, successors)) = self.call_stack.last_mut() {
+            let n = *n;
+            match successors.next() {
+                Some(m) if !self.index.contains_key(&m) => self.visit(g, m),
+                Some(m) => {
+                    if self.on_stack.contains(&m) {
+                        let low = self.lowlink[&n].min(self.index[&m]);
+                        self.lowlink.insert(n, low);
+                    }
+                }
+                None => {
+                    self.call_stack.pop();
+                    if let Some((parent, _)) = self.call_stack.last() {
+                        let low = self.lowlink[parent].min(self.lowlink[&n]);
+                        self.lowlink.insert(*parent, low);
+                    }
+                    if self.lowlink[&n] == self.index[&n] {
+                        // n is the root of a component, consisting of n and all nodes above it on the stack.
+                        let start = self.stack.iter().rposition(|m| *m == n).unwrap();
+                        let component: Vec<_> = self.stack.drain(start..).collect();
+                        for m in &component {
+                            self.on_stack.remove(m);
+                        }
+                        self.components.push(component);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Finds the strongly connected components of `g` with Tarjan's algorithm.
+///
+/// Returns the nodes of each component. Components are ordered so that all edges between
+/// components lead from later to earlier components, i.e. in reverse topological order.
+fn tarjan_scc<G, NW, EW>(g: &G) -> Vec<Vec<G::NodeRef>>
+where
+    G: Graph<NW, EW>,
+{
+    let mut tarjan = Tarjan {
+        index: HashMap::new(),
+        lowlink: HashMap::new(),
+        stack: vec![],
+        on_stack: HashSet::new(),
+        call_stack: vec![],
+        components: vec![],
+    };
+    for root in g.nodes() {
+        if !tarjan.index.contains_key(&root) {
+            tarjan.run(g, root);
+        }
+    }
+    tarjan.components
+}
+
+/// Splits `g` into its strongly connected components, i.e. the maximal subgraphs in which
+/// every node can be reached from every other node.
+///
+/// Returns a view of `g` for each component, containing its nodes and all edges between them.
+/// Components are ordered so that all edges between components lead from later to earlier components.
+///
+/// For undirected graphs, these are the same as the weakly connected components.
+pub fn strongly_connected_components<'g, NW, EW, G>(g: &'g G) -> Vec<ComponentView<'g, NW, EW, G>>
+where
+    G: Graph<NW, EW>,
+{
+    tarjan_scc(g)
+        .into_iter()
+        .map(|nodes| FilterMap::induced_subgraph(g, nodes))
+        .collect()
+}
+
+/// Builds the condensation of `g`, where each strongly connected component
+/// is contracted into a single node.
+///
+/// The nodes of the result hold the nodes of the respective component, in the same order as returned by
+/// [strongly_connected_components]. For each pair of connected components, the result contains a single edge,
+/// holding all edges of `g` between those components. As components contain all cycles, the result is acyclic.
+pub fn condensation<G, NW, EW>(g: &G) -> DiGraph<Vec<G::NodeRef>, Vec<G::EdgeRef>>
+where
+    G: Graph<NW, EW>,
+{
+    let components = tarjan_scc(g);
+    let mut result = DiGraph::with_capacity(components.len(), 0);
+    let mut component_of = HashMap::new();
+    for nodes in components {
+        let c = result.add_node(nodes);
+        for n in &result[c] {
+            component_of.insert(*n, c);
+        }
+    }
+
+    let mut edges: HashMap<(NodeIndex, NodeIndex), Vec<_>> = HashMap::new();
+    for e in g.edges() {
+        let (a, b) = g.adjacent_nodes(e);
+        let (a, b) = (component_of[&a], component_of[&b]);
+        if a != b {
+            edges.entry((a, b)).or_default().push(e);
+        }
+    }
+    let mut edges: Vec<_> = edges.into_iter().collect();
+    edges.sort_by_key(|(pair, _)| *pair);
+    for ((a, b), weight) in edges {
+        result.add_edge(a, b, weight);
+    }
+    result
+}
@@ -0,0 +1,21 @@
+//!
+//! This module provides graph algorithms that work on any [graph::Graph] implementation.
+//!
+//! Unlike the algorithms provided by petgraph, they can also be run on
+//! [filter_map::FilterMap] views and on the results of pattern matching.
+
+/// Breadth first, depth first and topological traversals.
+mod traversal;
+pub use traversal::*;
+
+/// Shortest paths and reachability.
+mod paths;
+pub use paths::*;
+
+/// Connected and strongly connected components.
+mod components;
+pub use components::*;
+
+/// Statistics about the structure and contents of graphs.
+mod statistics;
+pub use statistics::*;
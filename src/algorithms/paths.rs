@@ -0,0 +1,203 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    ops::Add,
+};
+
+use crate::graph::{opposite_node, Graph};
+
+use super::{edges_directed, neighbors, Direction};
+
+/// A path through a graph, found by one of the shortest path algorithms.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path<NodeRef, EdgeRef, Cost> {
+    /// The nodes along the path, starting with the start node and ending with the goal node.
+    pub nodes: Vec<NodeRef>,
+    /// The edges along the path. `edges[i]` connects `nodes[i]` and `nodes[i + 1]`.
+    pub edges: Vec<EdgeRef>,
+    /// The sum of the costs of all edges along the path.
+    pub cost: Cost,
+}
+
+/// Entry of the priority queue used by [astar].
+///
+/// Ordered by priority, reversed so that the max-heap [BinaryHeap] pops the lowest priority first.
+struct QueueEntry<Cost, NodeRef> {
+    priority: Cost,
+    cost: Cost,
+    node: NodeRef,
+}
+
+impl<Cost: PartialOrd, NodeRef> PartialEq for QueueEntry<Cost, NodeRef> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Cost: PartialOrd, NodeRef> Eq for QueueEntry<Cost, NodeRef> {}
+
+impl<Cost: PartialOrd, NodeRef> PartialOrd for QueueEntry<Cost, NodeRef> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Cost: PartialOrd, NodeRef> Ord for QueueEntry<Cost, NodeRef> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Incomparable costs, e.g. NaN, are treated as equal.
+        other
+            .priority
+            .partial_cmp(&self.priority)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Finds a shortest path from `start` to `goal` using Dijkstra's algorithm.
+///
+/// Edges are followed in the given direction, and `edge_cost` gives the cost of an edge
+/// based on its weight. Edge costs must not be negative. `Cost::default()` is used as zero cost.
+///
+/// Returns None if `goal` can't be reached from `start`.
+pub fn dijkstra<G, NW, EW, Cost, CostFn>(
+    g: &G,
+    start: G::NodeRef,
+    goal: G::NodeRef,
+    direction: Direction,
+    edge_cost: CostFn,
+) -> Option<Path<G::NodeRef, G::EdgeRef, Cost>>
+where
+    G: Graph<NW, EW>,
+    Cost: Copy + PartialOrd + Add<Output = Cost> + Default,
+    CostFn: Fn(&EW) -> Cost,
+{
+    astar(g, start, goal, direction, edge_cost, |_| Cost::default())
+}
+
+/// Finds a shortest path from `start` to `goal` using the A* algorithm.
+///
+/// Works like [dijkstra], but additionally uses `estimate_cost` to guide the search towards `goal`.
+/// `estimate_cost(n)` must not be larger than the cost of the shortest path from `n` to `goal`,
+/// otherwise the returned path might not be the shortest one.
+///
+/// Returns None if `goal` can't be reached from `start`.
+pub fn astar<G, NW, EW, Cost, CostFn, EstimateFn>(
+    g: &G,
+    start: G::NodeRef,
+    goal: G::NodeRef,
+    direction: Direction,
+    edge_cost: CostFn,
+    estimate_cost: EstimateFn,
+) -> Option<Path<G::NodeRef, G::EdgeRef, Cost>>
+where
+    G: Graph<NW, EW>,
+    Cost: Copy + PartialOrd + Add<Output = Cost> + Default,
+    CostFn: Fn(&EW) -> Cost,
+    EstimateFn: Fn(G::NodeRef) -> Cost,
+{
+    // Lowest known cost to reach each discovered node.
+    let mut best_costs = HashMap::from([(start, Cost::default())]);
+    // Last edge on the cheapest known path to each discovered node.
+    let mut predecessors = HashMap::new();
+    let mut queue = BinaryHeap::from([QueueEntry {
+        priority: estimate_cost(start),
+        cost: Cost::default(),
+        node: start,
+    }]);
+
+    while let Some(QueueEntry { cost, node, .. }) = queue.pop() {
+        if node == goal {
+            return Some(build_path(g, goal, cost, &predecessors));
+        }
+        // Skip outdated entries, the node has been reached more cheaply in the meantime.
+        if best_costs[&node] < cost {
+            continue;
+        }
+        for e in edges_directed(g, node, direction) {
+            let next = opposite_node(g, e, node);
+            let next_cost = cost + edge_cost(g.edge_weight(e));
+            if best_costs.get(&next).is_none_or(|c| next_cost < *c) {
+                best_costs.insert(next, next_cost);
+                predecessors.insert(next, e);
+                queue.push(QueueEntry {
+                    priority: next_cost + estimate_cost(next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Follows the predecessor edges back from `goal`, and builds the path leading there.
+fn build_path<G, NW, EW, Cost>(
+    g: &G,
+    goal: G::NodeRef,
+    cost: Cost,
+    predecessors: &HashMap<G::NodeRef, G::EdgeRef>,
+) -> Path<G::NodeRef, G::EdgeRef, Cost>
+where
+    G: Graph<NW, EW>,
+{
+    let mut nodes = vec![goal];
+    let mut edges = vec![];
+    let mut node = goal;
+    while let Some(e) = predecessors.get(&node) {
+        node = opposite_node(g, *e, node);
+        nodes.push(node);
+        edges.push(*e);
+    }
+    nodes.reverse();
+    edges.reverse();
+    Path { nodes, edges, cost }
+}
+
+/// Returns all nodes reachable from `start` by following at most `max_depth` edges
+/// in the given direction, together with the minimal number of edges needed to reach them.
+///
+/// `start` itself is included with depth 0.
+pub fn reachable_within<G, NW, EW>(
+    g: &G,
+    start: G::NodeRef,
+    max_depth: usize,
+    direction: Direction,
+) -> HashMap<G::NodeRef, usize>
+where
+    G: Graph<NW, EW>,
+{
+    reachable_within_from(g, [start], max_depth, direction)
+}
+
+/// Like [reachable_within], but starts at several nodes at once.
+/// The depth of a node is its distance to the closest start node.
+pub(crate) fn reachable_within_from<G, NW, EW>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::NodeRef>,
+    max_depth: usize,
+    direction: Direction,
+) -> HashMap<G::NodeRef, usize>
+where
+    G: Graph<NW, EW>,
+{
+    let mut depths = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if depths.insert(start, 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+    // Breadth first search reaches each node along a path with the fewest edges.
+    while let Some(n) = queue.pop_front() {
+        let depth = depths[&n];
+        if depth == max_depth {
+            continue;
+        }
+        for m in neighbors(g, n, direction) {
+            if let Entry::Vacant(entry) = depths.entry(m) {
+                entry.insert(depth + 1);
+                queue.push_back(m);
+            }
+        }
+    }
+    depths
+}
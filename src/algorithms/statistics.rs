@@ -0,0 +1,104 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::Graph;
+
+use super::{Bfs, Direction};
+
+/// Overview of the structure and contents of a graph, as computed by [summarize].
+///
+/// Distributions and histograms are stored as maps from a value to the number of elements
+/// with that value. To serialize a summary into JSON, the kinds must serialize to strings or numbers,
+/// e.g. unit enum variants.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphSummary<NodeKind: Ord, EdgeKind: Ord> {
+    /// Whether the graph is directed.
+    pub directed: bool,
+    /// Number of nodes.
+    pub node_count: usize,
+    /// Number of edges.
+    pub edge_count: usize,
+    /// Number of nodes for each in degree.
+    pub in_degrees: BTreeMap<usize, usize>,
+    /// Number of nodes for each out degree.
+    pub out_degrees: BTreeMap<usize, usize>,
+    /// Number of edges connecting a node to itself.
+    pub self_loops: usize,
+    /// Number of edges that connect the same nodes as an earlier edge, in the same direction.
+    pub parallel_edges: usize,
+    /// Number of weakly connected components.
+    pub components: usize,
+    /// Number of nodes of each kind.
+    pub node_kinds: BTreeMap<NodeKind, usize>,
+    /// Number of edges of each kind.
+    pub edge_kinds: BTreeMap<EdgeKind, usize>,
+}
+
+/// Computes a [GraphSummary] of `g`.
+///
+/// `node_kind` and `edge_kind` classify the nodes and edges of `g` by their weights,
+/// e.g. by returning the name of their enum variant.
+pub fn summarize<G, NW, EW, NodeKind, EdgeKind, NodeFn, EdgeFn>(
+    g: &G,
+    node_kind: NodeFn,
+    edge_kind: EdgeFn,
+) -> GraphSummary<NodeKind, EdgeKind>
+where
+    G: Graph<NW, EW>,
+    NodeKind: Ord,
+    EdgeKind: Ord,
+    NodeFn: Fn(&NW) -> NodeKind,
+    EdgeFn: Fn(&EW) -> EdgeKind,
+{
+    let mut summary = GraphSummary {
+        directed: g.is_directed(),
+        node_count: g.count_nodes(),
+        edge_count: g.count_edges(),
+        in_degrees: BTreeMap::new(),
+        out_degrees: BTreeMap::new(),
+        self_loops: 0,
+        parallel_edges: 0,
+        components: 0,
+        node_kinds: BTreeMap::new(),
+        edge_kinds: BTreeMap::new(),
+    };
+
+    let mut visited = HashSet::new();
+    for n in g.nodes() {
+        *summary.in_degrees.entry(g.in_degree(n)).or_default() += 1;
+        *summary.out_degrees.entry(g.out_degree(n)).or_default() += 1;
+        *summary
+            .node_kinds
+            .entry(node_kind(g.node_weight(n)))
+            .or_default() += 1;
+        if !visited.contains(&n) {
+            visited.extend(Bfs::new(g, n, Direction::Both));
+            summary.components += 1;
+        }
+    }
+
+    // Number of edges seen so far between each pair of nodes.
+    let mut connections: HashMap<_, usize> = HashMap::new();
+    for e in g.edges() {
+        let (a, b) = g.adjacent_nodes(e);
+        let key = if g.is_directed_edge(e) {
+            (a, b, true)
+        } else {
+            (a.min(b), a.max(b), false)
+        };
+        let count = connections.entry(key).or_default();
+        if *count > 0 {
+            summary.parallel_edges += 1;
+        }
+        *count += 1;
+        if a == b {
+            summary.self_loops += 1;
+        }
+        *summary
+            .edge_kinds
+            .entry(edge_kind(g.edge_weight(e)))
+            .or_default() += 1;
+    }
+    summary
+}
@@ -0,0 +1,324 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    marker::PhantomData,
+    vec,
+};
+
+use crate::graph::{opposite_node, outgoing_nodes, Graph};
+
+/// Selects which edges a traversal follows from a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Follow outgoing edges, from source to destination.
+    Outgoing,
+    /// Follow incoming edges, from destination to source.
+    Incoming,
+    /// Follow all adjacent edges, ignoring their direction.
+    Both,
+}
+
+/// Returns the edges of `n` that can be followed in the given direction.
+///
+/// For undirected graphs, all directions are equivalent.
+pub fn edges_directed<'a, G, NW: 'a, EW: 'a>(
+    g: &'a G,
+    n: G::NodeRef,
+    direction: Direction,
+) -> impl Iterator<Item = G::EdgeRef> + 'a
+where
+    G: Graph<NW, EW>,
+{
+    let outgoing = (direction == Direction::Outgoing).then(|| g.outgoing_edges(n));
+    let incoming = (direction == Direction::Incoming).then(|| g.incoming_edges(n));
+    let adjacent = (direction == Direction::Both).then(|| g.adjacent_edges(n));
+    outgoing
+        .into_iter()
+        .flatten()
+        .chain(incoming.into_iter().flatten())
+        .chain(adjacent.into_iter().flatten())
+}
+
+/// Returns the nodes reachable from `n` by following a single edge in the given direction.
+///
+/// Nodes connected by parallel edges are returned once per edge. For undirected graphs,
+/// all directions are equivalent.
+pub fn neighbors<'a, G, NW: 'a, EW: 'a>(
+    g: &'a G,
+    n: G::NodeRef,
+    direction: Direction,
+) -> impl Iterator<Item = G::NodeRef> + 'a
+where
+    G: Graph<NW, EW>,
+{
+    edges_directed(g, n, direction).map(move |e| opposite_node(g, e, n))
+}
+
+/// Breadth first traversal of a graph, yielding each reachable node once.
+///
+/// Nodes are yielded in order of their distance from the start nodes.
+pub struct Bfs<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    graph: &'g G,
+    direction: Direction,
+    /// Discovered nodes that haven't been yielded yet.
+    queue: VecDeque<G::NodeRef>,
+    /// All nodes that have been added to the queue so far.
+    discovered: HashSet<G::NodeRef>,
+    weights: PhantomData<fn() -> (NodeWeight, EdgeWeight)>,
+}
+
+impl<'g, NodeWeight, EdgeWeight, G> Bfs<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    /// Creates a breadth first traversal starting at `start`.
+    pub fn new(graph: &'g G, start: G::NodeRef, direction: Direction) -> Self {
+        Self::with_starts(graph, [start], direction)
+    }
+
+    /// Creates a breadth first traversal starting at all of the given nodes at once.
+    pub fn with_starts(
+        graph: &'g G,
+        starts: impl IntoIterator<Item = G::NodeRef>,
+        direction: Direction,
+    ) -> Self {
+        let mut discovered = HashSet::new();
+        let queue = starts
+            .into_iter()
+            .filter(|n| discovered.insert(*n))
+            .collect();
+        Self {
+            graph,
+            direction,
+            queue,
+            discovered,
+            weights: PhantomData,
+        }
+    }
+}
+
+impl<'g, NodeWeight, EdgeWeight, G> Iterator for Bfs<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    type Item = G::NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.queue.pop_front()?;
+        for m in neighbors(self.graph, n, self.direction) {
+            if self.discovered.insert(m) {
+                self.queue.push_back(m);
+            }
+        }
+        Some(n)
+    }
+}
+
+/// Depth first traversal of a graph, yielding each reachable node once,
+/// before any of its descendants.
+pub struct DfsPreorder<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    graph: &'g G,
+    direction: Direction,
+    /// Nodes to visit next, the last one first. May contain visited nodes.
+    stack: Vec<G::NodeRef>,
+    /// All nodes that have been yielded so far.
+    visited: HashSet<G::NodeRef>,
+    weights: PhantomData<fn() -> (NodeWeight, EdgeWeight)>,
+}
+
+impl<'g, NodeWeight, EdgeWeight, G> DfsPreorder<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    /// Creates a depth first traversal starting at `start`.
+    pub fn new(graph: &'g G, start: G::NodeRef, direction: Direction) -> Self {
+        Self::with_starts(graph, [start], direction)
+    }
+
+    /// Creates a depth first traversal that starts at each of the given nodes in turn,
+    /// skipping those visited before.
+    pub fn with_starts(
+        graph: &'g G,
+        starts: impl IntoIterator<Item = G::NodeRef>,
+        direction: Direction,
+    ) -> Self {
+        let mut stack: Vec<_> = starts.into_iter().collect();
+        stack.reverse();
+        Self {
+            graph,
+            direction,
+            stack,
+            visited: HashSet::new(),
+            weights: PhantomData,
+        }
+    }
+}
+
+impl<'g, NodeWeight, EdgeWeight, G> Iterator for DfsPreorder<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    type Item = G::NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(n) = self.stack.pop() {
+            if self.visited.insert(n) {
+                // Push in reverse, so that the first neighbor is visited first.
+                let start = self.stack.len();
+                self.stack.extend(
+                    neighbors(self.graph, n, self.direction).filter(|m| !self.visited.contains(m)),
+                );
+                self.stack[start..].reverse();
+                return Some(n);
+            }
+        }
+        None
+    }
+}
+
+/// Depth first traversal of a graph, yielding each reachable node once,
+/// after all of its descendants.
+pub struct DfsPostorder<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    graph: &'g G,
+    direction: Direction,
+    /// Start nodes that haven't been processed yet, the last one first.
+    starts: Vec<G::NodeRef>,
+    /// Path of nodes currently being explored, along with their unexplored neighbors.
+    stack: Vec<(G::NodeRef, vec::IntoIter<G::NodeRef>)>,
+    /// All nodes that have been added to the stack so far.
+    discovered: HashSet<G::NodeRef>,
+    weights: PhantomData<fn() -> (NodeWeight, EdgeWeight)>,
+}
+
+impl<'g, NodeWeight, EdgeWeight, G> DfsPostorder<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    /// Creates a depth first traversal starting at `start`.
+    pub fn new(graph: &'g G, start: G::NodeRef, direction: Direction) -> Self {
+        Self::with_starts(graph, [start], direction)
+    }
+
+    /// Creates a depth first traversal that starts at each of the given nodes in turn,
+    /// skipping those visited before.
+    pub fn with_starts(
+        graph: &'g G,
+        starts: impl IntoIterator<Item = G::NodeRef>,
+        direction: Direction,
+    ) -> Self {
+        let mut starts: Vec<_> = starts.into_iter().collect();
+        starts.reverse();
+        Self {
+            graph,
+            direction,
+            starts,
+            stack: vec![],
+            discovered: HashSet::new(),
+            weights: PhantomData,
+        }
+    }
+
+    /// Marks `n` as discovered, and starts exploring its neighbors.
+    fn discover(&mut self, n: G::NodeRef) {
+        self.discovered.insert(n);
+        let neighbors: Vec<_> = neighbors(self.graph, n, self.direction).collect();
+        self.stack.push((n, neighbors.into_iter()));
+    }
+}
+
+impl<'g, NodeWeight, EdgeWeight, G> Iterator for DfsPostorder<'g, NodeWeight, EdgeWeight, G>
+where
+    G: Graph<NodeWeight, EdgeWeight>,
+{
+    type Item = G::NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut() {
+                Some((n, unexplored)) => match unexplored.next() {
+                    Some(m) => {
+                        if !self.discovered.contains(&m) {
+                            self.discover(m);
+                        }
+                    }
+                    None => {
+                        let n = *n;
+                        self.stack.pop();
+                        return Some(n);
+                    }
+                },
+                None => {
+                    let start = self.starts.pop()?;
+                    if !self.discovered.contains(&start) {
+                        self.discover(start);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Error returned by [toposort] if the graph contains a cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle<NodeRef>(NodeRef);
+
+impl<NodeRef: Copy> Cycle<NodeRef> {
+    /// Returns a node that is part of the cycle.
+    pub fn node(&self) -> NodeRef {
+        self.0
+    }
+}
+
+/// Sorts the nodes of `g` topologically, so that every edge leads from
+/// an earlier node to a later one.
+///
+/// Returns a [Cycle] error if there is no such order. For undirected graphs,
+/// this is the case as soon as the graph has any edges.
+pub fn toposort<G, NW, EW>(g: &G) -> Result<Vec<G::NodeRef>, Cycle<G::NodeRef>>
+where
+    G: Graph<NW, EW>,
+{
+    let mut order = Vec::with_capacity(g.count_nodes());
+    let mut finished = HashSet::new();
+    // Nodes on the current depth first path. Reaching one of them again closes a cycle.
+    let mut on_path = HashSet::new();
+
+    for root in g.nodes() {
+        if finished.contains(&root) {
+            continue;
+        }
+        let successors = |n| outgoing_nodes(g, n).collect::<Vec<_>>().into_iter();
+        let mut stack = vec![(root, successors(root))];
+        on_path.insert(root);
+
+        while let Some((n, unexplored)) = stack.last_mut() {
+            match unexplored.next() {
+                Some(m) if on_path.contains(&m) => return Err(Cycle(m)),
+                Some(m) => {
+                    if !finished.contains(&m) {
+                        on_path.insert(m);
+                        stack.push((m, successors(m)));
+                    }
+                }
+                None => {
+                    let n = *n;
+                    stack.pop();
+                    on_path.remove(&n);
+                    finished.insert(n);
+                    order.push(n);
+                }
+            }
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
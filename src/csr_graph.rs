@@ -0,0 +1,385 @@
+//!
+//! This module provides [csr_graph::CsrGraph], an immutable graph storage backend using
+//! the compressed sparse row (CSR) layout.
+//!
+//! A CSR graph stores the edges of all nodes in a few contiguous arrays,
+//! sorted by their source (respectively target) nodes. This keeps memory usage low
+//! for large graphs, and makes queries that the pattern matching algorithms rely on
+//! cheap: iterating over the incoming and outgoing edges of a node is a slice access,
+//! and testing whether two nodes are connected is a binary search.
+//!
+//! A [csr_graph::CsrGraph] is created from any existing [graph::Graph] with [csr_graph::CsrGraph::from_graph],
+//! and can't be modified afterwards.
+
+use std::{collections::HashMap, ops::Range};
+
+use crate::graph::{self};
+
+/// Node reference type of [CsrGraph].
+///
+/// Nodes are numbered densely from `0` to `n - 1`, in the order in which they were yielded by
+/// the graph that the CSR graph was created from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CsrNodeIndex(u32);
+
+impl CsrNodeIndex {
+    /// Creates a node reference from its position.
+    pub fn new(index: usize) -> Self {
+        Self(to_u32(index))
+    }
+
+    /// Returns the position of this node.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Edge reference type of [CsrGraph].
+///
+/// Edges are numbered densely from `0` to `m - 1`, ordered by their source and target nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CsrEdgeIndex(u32);
+
+impl CsrEdgeIndex {
+    /// Creates an edge reference from its position.
+    pub fn new(index: usize) -> Self {
+        Self(to_u32(index))
+    }
+
+    /// Returns the position of this edge.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Converts an index to the 32 bit representation used internally.
+fn to_u32(index: usize) -> u32 {
+    u32::try_from(index).expect("CsrGraph supports at most 2^32 - 1 nodes and edges.")
+}
+
+/// Immutable graph in compressed sparse row layout, optimized for read access.
+///
+/// Edges are sorted by `(source, target)`, so that the outgoing edges of node `n`
+/// are exactly the edges `out_offsets[n]..out_offsets[n + 1]`, and their targets are sorted.
+/// For incoming edges, a second index sorted by `(target, source)` is kept.
+///
+/// Both directed and undirected graphs are supported. For undirected graphs,
+/// each edge is stored once, but reported as incoming and outgoing edge of both of its nodes.
+pub struct CsrGraph<NodeWeight, EdgeWeight> {
+    directed: bool,
+    node_weights: Vec<NodeWeight>,
+    edge_weights: Vec<EdgeWeight>,
+    /// `out_offsets[n]..out_offsets[n + 1]` is the range of edges starting at node `n`.
+    out_offsets: Vec<u32>,
+    /// Source node of each edge.
+    sources: Vec<u32>,
+    /// Target node of each edge. Sorted within the outgoing range of each node.
+    targets: Vec<u32>,
+    /// `in_offsets[n]..in_offsets[n + 1]` is the range of `in_edges` ending at node `n`.
+    in_offsets: Vec<u32>,
+    /// Edges sorted by `(target, source)`.
+    in_edges: Vec<u32>,
+}
+
+impl<NodeWeight, EdgeWeight> CsrGraph<NodeWeight, EdgeWeight> {
+    /// Creates a new CSR graph containing copies of all nodes and edges of `graph`.
+    ///
+    /// The `i`-th node yielded by `graph.nodes()` becomes the node `CsrNodeIndex::new(i)`.
+    /// Parallel edges keep the relative order in which `graph.edges()` yields them.
+    ///
+    /// ## Panics:
+    /// Panics if `graph` has `2^32` or more nodes or edges.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: graph::Graph<NodeWeight, EdgeWeight>,
+        NodeWeight: Clone,
+        EdgeWeight: Clone,
+    {
+        let nodes: Vec<_> = graph.nodes().collect();
+        let node_count = to_u32(nodes.len()) as usize;
+        let positions: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (*n, i as u32))
+            .collect();
+
+        let mut edges: Vec<_> = graph
+            .edges()
+            .map(|e| {
+                let (a, b) = graph.adjacent_nodes(e);
+                (positions[&a], positions[&b], e)
+            })
+            .collect();
+        // Fail early if the edges can't be indexed.
+        to_u32(edges.len());
+        // Stable sort, keeps parallel edges in order.
+        edges.sort_by_key(|(a, b, _)| (*a, *b));
+
+        let mut out_offsets = vec![0; node_count + 1];
+        let mut in_offsets = vec![0; node_count + 1];
+        for (a, b, _) in &edges {
+            out_offsets[*a as usize + 1] += 1;
+            in_offsets[*b as usize + 1] += 1;
+        }
+        for n in 0..node_count {
+            out_offsets[n + 1] += out_offsets[n];
+            in_offsets[n + 1] += in_offsets[n];
+        }
+
+        // Counting sort by target. As edges are visited ordered by source,
+        // the sources within each target range end up sorted as well.
+        let mut in_edges = vec![0; edges.len()];
+        let mut next_slot = in_offsets.clone();
+        for (e, (_, b, _)) in edges.iter().enumerate() {
+            let slot = &mut next_slot[*b as usize];
+            in_edges[*slot as usize] = e as u32;
+            *slot += 1;
+        }
+
+        Self {
+            directed: graph.is_directed(),
+            node_weights: nodes
+                .iter()
+                .map(|n| graph.node_weight(*n).clone())
+                .collect(),
+            edge_weights: edges
+                .iter()
+                .map(|(_, _, e)| graph.edge_weight(*e).clone())
+                .collect(),
+            out_offsets,
+            sources: edges.iter().map(|(a, _, _)| *a).collect(),
+            targets: edges.iter().map(|(_, b, _)| *b).collect(),
+            in_offsets,
+            in_edges,
+        }
+    }
+
+    /// Range of the edges starting at `node`.
+    fn out_range(&self, node: CsrNodeIndex) -> Range<usize> {
+        assert!(node.index() < self.node_weights.len());
+        self.out_offsets[node.index()] as usize..self.out_offsets[node.index() + 1] as usize
+    }
+
+    /// Slice of the edges ending at `node`.
+    fn in_slice(&self, node: CsrNodeIndex) -> &[u32] {
+        assert!(node.index() < self.node_weights.len());
+        &self.in_edges
+            [self.in_offsets[node.index()] as usize..self.in_offsets[node.index() + 1] as usize]
+    }
+
+    /// Checks whether `edge` starts and ends at the same node.
+    fn is_self_loop(&self, edge: usize) -> bool {
+        self.sources[edge] == self.targets[edge]
+    }
+
+    /// Edges starting at `node`, independent of the graph's directedness.
+    fn stored_outgoing(&self, node: CsrNodeIndex) -> impl Iterator<Item = CsrEdgeIndex> + '_ {
+        self.out_range(node).map(|e| CsrEdgeIndex(e as u32))
+    }
+
+    /// Edges ending at `node`, independent of the graph's directedness.
+    fn stored_incoming(&self, node: CsrNodeIndex) -> impl Iterator<Item = CsrEdgeIndex> + '_ {
+        self.in_slice(node).iter().map(|e| CsrEdgeIndex(*e))
+    }
+
+    /// Number of edges at `node` in an undirected graph, counting self loops once.
+    fn undirected_degree(&self, node: CsrNodeIndex) -> usize {
+        let self_loops = self.find_edges(node, node).map_or(0, |range| range.len());
+        self.out_range(node).len() + self.in_slice(node).len() - self_loops
+    }
+
+    /// Returns the range of edges stored from `from` to `to` using binary search.
+    fn find_edges(&self, from: CsrNodeIndex, to: CsrNodeIndex) -> Option<Range<usize>> {
+        let range = self.out_range(from);
+        let targets = &self.targets[range.clone()];
+        let start = targets.partition_point(|t| *t < to.0);
+        let end = targets.partition_point(|t| *t <= to.0);
+        (start < end).then(|| range.start + start..range.start + end)
+    }
+
+    /// Finds an edge from `from` to `to` in O(log d), where d is the out degree of `from`.
+    ///
+    /// For undirected graphs, edges from `to` to `from` are found as well.
+    /// If there are parallel edges, the first one is returned.
+    pub fn find_edge(&self, from: CsrNodeIndex, to: CsrNodeIndex) -> Option<CsrEdgeIndex> {
+        self.find_edges(from, to)
+            .or_else(|| {
+                (!self.directed)
+                    .then(|| self.find_edges(to, from))
+                    .flatten()
+            })
+            .map(|range| CsrEdgeIndex(range.start as u32))
+    }
+
+    /// Checks whether there is an edge from `from` to `to`, see [CsrGraph::find_edge].
+    pub fn contains_edge_between(&self, from: CsrNodeIndex, to: CsrNodeIndex) -> bool {
+        self.find_edge(from, to).is_some()
+    }
+}
+
+impl<NodeWeight, EdgeWeight> graph::Graph<NodeWeight, EdgeWeight>
+    for CsrGraph<NodeWeight, EdgeWeight>
+{
+    type NodeRef = CsrNodeIndex;
+
+    type EdgeRef = CsrEdgeIndex;
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn is_directed_edge(&self, edge: Self::EdgeRef) -> bool {
+        assert!(edge.index() < self.edge_weights.len());
+        self.directed
+    }
+
+    type AdjacentEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn adjacent_edges(&self, node: Self::NodeRef) -> Self::AdjacentEdgesIterator<'_> {
+        // Directed graphs report self loops twice, in line with the petgraph backend.
+        self.stored_incoming(node).chain(
+            self.stored_outgoing(node)
+                .filter(|e| self.directed || !self.is_self_loop(e.index())),
+        )
+    }
+
+    type IncomingEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn incoming_edges(&self, node: Self::NodeRef) -> Self::IncomingEdgesIterator<'_> {
+        self.stored_incoming(node).chain(
+            self.stored_outgoing(node)
+                .filter(|e| !self.directed && !self.is_self_loop(e.index())),
+        )
+    }
+
+    type OutgoingEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn outgoing_edges(&self, node: Self::NodeRef) -> Self::OutgoingEdgesIterator<'_> {
+        self.stored_outgoing(node).chain(
+            self.stored_incoming(node)
+                .filter(|e| !self.directed && !self.is_self_loop(e.index())),
+        )
+    }
+
+    /// Takes O(1) for directed graphs. For undirected graphs, this counts all edges at `node`,
+    /// which takes O(log d) to account for self loops.
+    fn in_degree(&self, node: Self::NodeRef) -> usize {
+        if self.directed {
+            self.in_slice(node).len()
+        } else {
+            self.undirected_degree(node)
+        }
+    }
+
+    /// Takes O(1) for directed graphs, see `in_degree`.
+    fn out_degree(&self, node: Self::NodeRef) -> usize {
+        if self.directed {
+            self.out_range(node).len()
+        } else {
+            self.undirected_degree(node)
+        }
+    }
+
+    /// Takes O(1) for directed graphs, see `in_degree`.
+    fn degree(&self, node: Self::NodeRef) -> usize {
+        if self.directed {
+            self.out_range(node).len() + self.in_slice(node).len()
+        } else {
+            self.undirected_degree(node)
+        }
+    }
+
+    fn adjacent_nodes(&self, edge: Self::EdgeRef) -> (Self::NodeRef, Self::NodeRef) {
+        assert!(
+            edge.index() < self.edge_weights.len(),
+            "Couldn't find edge endpoint references: Edge reference invalid."
+        );
+        (
+            CsrNodeIndex(self.sources[edge.index()]),
+            CsrNodeIndex(self.targets[edge.index()]),
+        )
+    }
+
+    fn node_weight(&self, node: Self::NodeRef) -> &NodeWeight {
+        self.node_weights
+            .get(node.index())
+            .expect("Couldn't find node weight: Node reference invalid.")
+    }
+
+    fn edge_weight(&self, edge: Self::EdgeRef) -> &EdgeWeight {
+        self.edge_weights
+            .get(edge.index())
+            .expect("Couldn't find edge weight: Edge reference invalid.")
+    }
+
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        node.index() < self.node_weights.len()
+    }
+
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        edge.index() < self.edge_weights.len()
+    }
+
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        self.node_weights.get(node.index())
+    }
+
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        self.edge_weights.get(edge.index())
+    }
+
+    type NodeWeightsIterator<'a>
+        = impl Iterator<Item = &'a NodeWeight>
+    where
+        Self: 'a,
+        NodeWeight: 'a;
+
+    fn node_weights(&self) -> Self::NodeWeightsIterator<'_> {
+        self.node_weights.iter()
+    }
+
+    type EdgeWeightsIterator<'a>
+        = impl Iterator<Item = &'a EdgeWeight>
+    where
+        Self: 'a,
+        EdgeWeight: 'a;
+
+    fn edge_weights(&self) -> Self::EdgeWeightsIterator<'_> {
+        self.edge_weights.iter()
+    }
+
+    type NodesIterator<'a>
+        = impl Iterator<Item = Self::NodeRef> + 'a
+    where
+        Self: 'a;
+
+    fn nodes(&self) -> Self::NodesIterator<'_> {
+        (0..self.node_weights.len() as u32).map(CsrNodeIndex)
+    }
+
+    type EdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn edges(&self) -> Self::EdgesIterator<'_> {
+        (0..self.edge_weights.len() as u32).map(CsrEdgeIndex)
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.node_weights.len()
+    }
+
+    fn count_edges(&self) -> usize {
+        self.edge_weights.len()
+    }
+}
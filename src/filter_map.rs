@@ -13,16 +13,19 @@
 
 use std::collections::HashMap;
 
-use crate::graph::{self};
+use crate::{
+    algorithms::{reachable_within_from, Direction},
+    graph::{self, opposite_node},
+};
 
 /// `FilterMap` is a graph representation that is designed to abstractly
 /// implement a wide range of possible Queries on a `Graph` object.
 ///
 /// Given an underlying base graph object, it can:
 /// * Create a subgraph, filtering out nodes and edges based on a provided
-/// condition. Indices remain stable under those transformations.
+///   condition. Indices remain stable under those transformations.
 /// * Apply transformations to the node and edge weights. The new weights can
-/// reference the weights of the old graph as rust objects.
+///   reference the weights of the old graph as rust objects.
 ///
 /// However it is not possible to change the structure beyond that.
 ///
@@ -219,6 +222,73 @@ impl<'g, NodeWeight, EdgeWeight, Graph: graph::Graph<NodeWeight, EdgeWeight>>
             },
         )
     }
+
+    /// Creates the subgraph of the base graph induced by `nodes`, i.e. containing
+    /// the given nodes and all edges between them.
+    ///
+    /// Like `weight_filter`, the new graph references the weights of the base graph.
+    pub fn induced_subgraph(
+        base_graph: &'g Graph,
+        nodes: impl IntoIterator<Item = Graph::NodeRef>,
+    ) -> Self
+    where
+        NodeWeight: 'g,
+        EdgeWeight: 'g,
+    {
+        let node_map: HashMap<_, _> = nodes
+            .into_iter()
+            .map(|n| (n, base_graph.node_weight(n)))
+            .collect();
+        let edge_map = node_map
+            .keys()
+            .flat_map(|n| base_graph.adjacent_edges(*n).map(move |e| (*n, e)))
+            // keep edges whose other end is part of the subgraph too
+            .filter(|(n, e)| node_map.contains_key(&opposite_node(base_graph, *e, *n)))
+            .map(|(_, e)| (e, base_graph.edge_weight(e)))
+            .collect();
+
+        Self {
+            base_graph,
+            node_map,
+            edge_map,
+        }
+    }
+
+    /// Creates the neighborhood of the `seeds` nodes in the base graph, i.e. the subgraph induced
+    /// by all nodes that can be reached from one of the seeds by following at most `k` edges in the given direction.
+    pub fn k_hop_neighborhood(
+        base_graph: &'g Graph,
+        seeds: impl IntoIterator<Item = Graph::NodeRef>,
+        k: usize,
+        direction: Direction,
+    ) -> Self
+    where
+        NodeWeight: 'g,
+        EdgeWeight: 'g,
+    {
+        let nodes = reachable_within_from(base_graph, seeds, k, direction);
+        Self::induced_subgraph(base_graph, nodes.into_keys())
+    }
+
+    /// Creates the neighborhood of a pattern match in the base graph it was found in,
+    /// i.e. the `k` hop neighborhood of all matched nodes.
+    ///
+    /// `matched_nodes` maps pattern nodes to the base graph nodes they have been matched to,
+    /// as found in the [crate::pattern_matching::MatchedRefs] returned by
+    /// [crate::pattern_matching::solve_vf_with_refs].
+    pub fn match_context<NRef>(
+        base_graph: &'g Graph,
+        matched_nodes: &HashMap<NRef, Graph::NodeRef>,
+        k: usize,
+        direction: Direction,
+    ) -> Self
+    where
+        NodeWeight: 'g,
+        EdgeWeight: 'g,
+    {
+        let seeds = matched_nodes.values().copied();
+        Self::k_hop_neighborhood(base_graph, seeds, k, direction)
+    }
 }
 
 /// Filters nodes and edges based on the provided patterns.
@@ -326,6 +396,28 @@ impl<
         &self.edge_map[&edge]
     }
 
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        self.node_map.contains_key(&node)
+    }
+
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        self.edge_map.contains_key(&edge)
+    }
+
+    fn try_adjacent_nodes(&self, edge: Self::EdgeRef) -> Option<(Self::NodeRef, Self::NodeRef)> {
+        // Dangling edges are ruled out on construction, so the nodes are contained too.
+        self.contains_edge(edge)
+            .then(|| self.base_graph.adjacent_nodes(edge))
+    }
+
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        self.node_map.get(&node)
+    }
+
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        self.edge_map.get(&edge)
+    }
+
     type NodeWeightsIterator<'a> = impl Iterator<Item =&'a NodeWeight>
     where
         Self: 'a,
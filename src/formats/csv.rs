@@ -0,0 +1,391 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+};
+
+use ::csv::{Reader, ReaderBuilder, StringRecord, Writer, WriterBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::graph::{Graph, GraphIoError, GraphMut};
+
+use super::{direction_name, text_fields::weight_from_text, to_values};
+
+/// Name of the column holding weights that don't serialize to a struct or map.
+const WEIGHT_COLUMN: &str = "weight";
+
+/// Column names and delimiter of CSV node and edge lists, as used by [read_csv] and [write_csv].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Column of the node list holding the node IDs, `id` by default.
+    pub id_column: String,
+    /// Column of the edge list holding the IDs of the source nodes, `source` by default.
+    pub source_column: String,
+    /// Column of the edge list holding the IDs of the target nodes, `target` by default.
+    pub target_column: String,
+    /// Optional column of the edge list telling whether each edge is directed, `directed` by default.
+    pub directed_column: String,
+    /// Field delimiter of both lists, `,` by default.
+    pub delimiter: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            id_column: "id".to_string(),
+            source_column: "source".to_string(),
+            target_column: "target".to_string(),
+            directed_column: "directed".to_string(),
+            delimiter: b',',
+        }
+    }
+}
+
+/// Writes the nodes of `g` to the CSV node list `nodes`, and its edges to the CSV edge list `edges`.
+/// Both lists start with a header row naming the columns.
+///
+/// Weights are serialized through serde. If all weights of the nodes or edges serialize to structs or maps,
+/// each field gets its own column, otherwise the whole weights are written to a `weight` column.
+/// Strings are written as they are, nested values as JSON text and missing fields or None as empty fields.
+///
+/// Nodes are identified by their position in [Graph::nodes], unless their weights have a field named
+/// like the ID column. The values of that field are used as IDs instead, and must be unique.
+/// Edge weights must not have fields named like the source, target or direction column.
+///
+/// If the direction of some edges differs from the direction of the graph, the edge list gets
+/// a direction column holding `true` for directed and `false` for undirected edges.
+pub fn write_csv<G, NW, EW, WN, WE>(
+    g: &G,
+    nodes: WN,
+    edges: WE,
+    options: &CsvOptions,
+) -> Result<(), GraphIoError>
+where
+    G: Graph<NW, EW>,
+    NW: Serialize,
+    EW: Serialize,
+    WN: Write,
+    WE: Write,
+{
+    let node_refs: Vec<_> = g.nodes().collect();
+    let node_values = to_values(node_refs.iter().map(|n| g.node_weight(*n)))?;
+    let mut node_columns = Columns::infer(&node_values);
+    let ids = if node_columns.by_field && node_columns.names.contains(&options.id_column) {
+        let ids = node_values
+            .iter()
+            .map(|value| match value.get(&options.id_column) {
+                None | Some(Value::Null) => Err(GraphIoError::schema(format!(
+                    "Node weight {value} has no {:?} field",
+                    options.id_column
+                ))),
+                Some(id) => Ok(format_field(id)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut seen = HashSet::new();
+        if let Some(id) = ids.iter().find(|id| !seen.insert(*id)) {
+            return Err(GraphIoError::schema(format!("Duplicate node ID {id:?}")));
+        }
+        ids
+    } else {
+        node_columns.names.insert(0, options.id_column.clone());
+        (0..node_refs.len()).map(|i| i.to_string()).collect()
+    };
+
+    let mut writer = csv_writer(nodes, options);
+    writer
+        .write_record(&node_columns.names)
+        .map_err(csv_error)?;
+    for (id, value) in ids.iter().zip(&node_values) {
+        let record = node_columns.names.iter().map(|name| {
+            if *name == options.id_column {
+                id.clone()
+            } else {
+                node_columns.field(value, name)
+            }
+        });
+        writer.write_record(record).map_err(csv_error)?;
+    }
+    writer.flush()?;
+
+    let edge_refs: Vec<_> = g.edges().collect();
+    let edge_values = to_values(edge_refs.iter().map(|e| g.edge_weight(*e)))?;
+    let edge_columns = Columns::infer(&edge_values);
+    for column in [
+        &options.source_column,
+        &options.target_column,
+        &options.directed_column,
+    ] {
+        if edge_columns.names.contains(column) {
+            return Err(GraphIoError::schema(format!(
+                "Edge weights have a field named like the column {column:?}"
+            )));
+        }
+    }
+    let positions: HashMap<_, _> = node_refs.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let edge_directions = edge_refs
+        .iter()
+        .any(|e| g.is_directed_edge(*e) != g.is_directed());
+
+    let mut writer = csv_writer(edges, options);
+    let mut header = vec![&options.source_column, &options.target_column];
+    if edge_directions {
+        header.push(&options.directed_column);
+    }
+    writer
+        .write_record(header.into_iter().chain(&edge_columns.names))
+        .map_err(csv_error)?;
+    for (e, value) in edge_refs.iter().zip(&edge_values) {
+        let (a, b) = g.adjacent_nodes(*e);
+        let mut keys = vec![ids[positions[&a]].clone(), ids[positions[&b]].clone()];
+        if edge_directions {
+            keys.push(g.is_directed_edge(*e).to_string());
+        }
+        let fields = edge_columns
+            .names
+            .iter()
+            .map(|name| edge_columns.field(value, name));
+        writer
+            .write_record(keys.into_iter().chain(fields))
+            .map_err(csv_error)?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// The weight columns of a node or edge list.
+struct Columns {
+    names: Vec<String>,
+    /// Whether weights are split into one column per field, or written to [WEIGHT_COLUMN].
+    by_field: bool,
+}
+
+impl Columns {
+    /// Chooses the columns for the given serialized weights, in order of the first appearance of each field.
+    fn infer(values: &[Value]) -> Self {
+        let by_field = values.iter().all(|v| v.is_object() || v.is_null());
+        let mut names: Vec<String> = vec![];
+        if by_field {
+            for name in values
+                .iter()
+                .filter_map(Value::as_object)
+                .flat_map(|o| o.keys())
+            {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        } else {
+            names.push(WEIGHT_COLUMN.to_string());
+        }
+        Columns { names, by_field }
+    }
+
+    /// Returns the text of the field in column `name` of a serialized weight.
+    fn field(&self, value: &Value, name: &str) -> String {
+        if self.by_field {
+            value.get(name).map(format_field).unwrap_or_default()
+        } else {
+            format_field(value)
+        }
+    }
+}
+
+/// Formats a serialized field as the text of a CSV field.
+fn format_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// Reads a graph from the CSV node list `nodes` and the CSV edge list `edges`,
+/// as written by [write_csv]. Both lists must start with a header row naming the columns.
+///
+/// The nodes are identified by the values in their ID column, which must be unique.
+/// Edges refer to them by these IDs in their source and target columns.
+///
+/// If the edge list has a direction column, edges with `true` in it are added as directed
+/// and edges with `false` as undirected ones, see [GraphMut::try_add_edge_with_direction].
+/// Edges without a direction get the direction of `G`.
+///
+/// Weights are deserialized through serde from all fields of a row, by column name.
+/// Fields are parsed as needed by the weight type, e.g. into numbers, or as JSON for nested values.
+/// Empty fields are read as None. If a weight can't be deserialized from the fields, it is deserialized
+/// from the only column besides the ID, source and target columns, or from an empty value if there is no such column.
+pub fn read_csv<G, NW, EW, RN, RE>(
+    nodes: RN,
+    edges: RE,
+    options: &CsvOptions,
+) -> Result<G, GraphIoError>
+where
+    G: GraphMut<NW, EW> + Default,
+    NW: DeserializeOwned,
+    EW: DeserializeOwned,
+    RN: Read,
+    RE: Read,
+{
+    let mut graph = G::default();
+    let mut node_refs = HashMap::new();
+
+    let mut reader = csv_reader(nodes, options);
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let [id_column] = column_positions(&headers, [&options.id_column], "node")?;
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        let line = line(&record);
+        let weight =
+            read_weight(&headers, &record, &[id_column]).map_err(|e| GraphIoError::Schema {
+                line: Some(line),
+                message: format!("Couldn't deserialize node weight: {e}"),
+            })?;
+        let id = &record[id_column];
+        if node_refs.contains_key(id) {
+            return Err(GraphIoError::Schema {
+                line: Some(line),
+                message: format!("Duplicate node ID {id:?}"),
+            });
+        }
+        node_refs.insert(id.to_string(), graph.add_node(weight));
+    }
+
+    let mut reader = csv_reader(edges, options);
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let [source_column, target_column] = column_positions(
+        &headers,
+        [&options.source_column, &options.target_column],
+        "edge",
+    )?;
+    let directed_column = headers.iter().position(|h| h == options.directed_column);
+    let key_columns: Vec<_> = [source_column, target_column]
+        .into_iter()
+        .chain(directed_column)
+        .collect();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        let line = line(&record);
+        let endpoint = |column: usize| {
+            node_refs
+                .get(&record[column])
+                .copied()
+                .ok_or_else(|| GraphIoError::DanglingEdge {
+                    line: Some(line),
+                    node: record[column].to_string(),
+                })
+        };
+        let (a, b) = (endpoint(source_column)?, endpoint(target_column)?);
+        let directed = graph.is_directed();
+        let edge_directed = match directed_column.map(|column| &record[column]) {
+            None | Some("") => directed,
+            Some("true") => true,
+            Some("false") => false,
+            Some(other) => {
+                return Err(GraphIoError::Schema {
+                    line: Some(line),
+                    message: format!("Invalid edge direction {other:?}"),
+                })
+            }
+        };
+        let weight =
+            read_weight(&headers, &record, &key_columns).map_err(|e| GraphIoError::Schema {
+                line: Some(line),
+                message: format!("Couldn't deserialize edge weight: {e}"),
+            })?;
+        if graph
+            .try_add_edge_with_direction(a, b, weight, edge_directed)
+            .is_none()
+        {
+            return Err(GraphIoError::Schema {
+                line: Some(line),
+                message: format!(
+                    "Edge is {}, but the graph is {}",
+                    direction_name(edge_directed),
+                    direction_name(directed)
+                ),
+            });
+        }
+    }
+    Ok(graph)
+}
+
+/// Deserializes a weight from a row of a node or edge list.
+///
+/// `key_columns` are the positions of the columns identifying nodes,
+/// which aren't used as fallback for weights that aren't structs or maps.
+fn read_weight<W: DeserializeOwned>(
+    headers: &StringRecord,
+    record: &StringRecord,
+    key_columns: &[usize],
+) -> Result<W, serde::de::value::Error> {
+    let fields: Vec<_> = headers.iter().zip(record).collect();
+    let mut other_fields = fields
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !key_columns.contains(i))
+        .map(|(_, (_, value))| *value);
+    let fallback = match (other_fields.next(), other_fields.next()) {
+        (None, _) => Some(""),
+        (Some(value), None) => Some(value),
+        _ => None,
+    };
+    weight_from_text(&fields, fallback)
+}
+
+/// Returns the positions of the columns with the given names in the header row of a node or edge list.
+fn column_positions<const N: usize>(
+    headers: &StringRecord,
+    names: [&String; N],
+    list: &str,
+) -> Result<[usize; N], GraphIoError> {
+    let mut positions = [0; N];
+    for (position, name) in positions.iter_mut().zip(names) {
+        *position = headers.iter().position(|h| h == name).ok_or_else(|| {
+            GraphIoError::schema(format!("The {list} list has no column {name:?}"))
+        })?;
+    }
+    Ok(positions)
+}
+
+/// Returns the line of the input a record was read from, starting at 1 for the header row.
+fn line(record: &StringRecord) -> u64 {
+    record.position().map_or(0, |p| p.line())
+}
+
+fn csv_reader<R: Read>(reader: R, options: &CsvOptions) -> Reader<R> {
+    ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .from_reader(reader)
+}
+
+fn csv_writer<W: Write>(writer: W, options: &CsvOptions) -> Writer<W> {
+    WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer)
+}
+
+/// Converts errors of the csv crate, keeping the line of malformed records.
+fn csv_error(e: ::csv::Error) -> GraphIoError {
+    let (line, message) = match e.kind() {
+        ::csv::ErrorKind::UnequalLengths {
+            pos: Some(pos),
+            expected_len,
+            len,
+        } => (
+            pos.line(),
+            format!("Expected {expected_len} fields, found {len}"),
+        ),
+        ::csv::ErrorKind::Utf8 {
+            pos: Some(pos),
+            err,
+        } => (pos.line(), format!("Invalid UTF-8: {err}")),
+        _ if e.is_io_error() => match e.into_kind() {
+            ::csv::ErrorKind::Io(e) => return GraphIoError::Io(e),
+            _ => unreachable!(),
+        },
+        _ => return GraphIoError::schema(e.to_string()),
+    };
+    GraphIoError::Syntax {
+        line,
+        column: None,
+        message,
+    }
+}
@@ -0,0 +1,526 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+};
+
+use serde::de::{self, DeserializeOwned};
+
+use crate::graph::{GraphIoError, GraphMut};
+
+use super::text_fields::weight_from_text;
+
+/// Attributes of a node or edge in a DOT file, by name.
+///
+/// Can be used as node or edge weight for [read_dot], to get the attributes as they are.
+pub type DotAttributes = BTreeMap<String, String>;
+
+/// Reads a graph in Graphviz DOT format from `reader`.
+///
+/// Weights are deserialized through serde from the attributes of each node and edge.
+/// Attribute values are parsed as needed by the weight type, e.g. into numbers, or as JSON for nested values. If a weight
+/// can't be deserialized from the attributes, it is deserialized from the `label` attribute alone,
+/// or from an empty value if there are no attributes at all. Nodes without a `label` attribute
+/// are labeled with their ID, as in Graphviz.
+///
+/// Default attributes set by `node` and `edge` statements, subgraphs, and edges between subgraphs are supported.
+/// Graph attributes and ports are ignored. In `strict` graphs, repeated edges update the attributes of the first one.
+///
+/// `G` must be able to hold edges of the direction of the graph, see [crate::graph::GraphMut::supports_edge_direction].
+/// A [crate::mixed_graph::MixedGraph] can hold both, and only the first graph of the file is read.
+/// Reading the output of [graph::VizDotGraph::print] gives the same graph, with the printed weights as labels.
+///
+/// [graph::VizDotGraph::print]: crate::graph::VizDotGraph::print
+pub fn read_dot<G, NW, EW, R>(mut reader: R) -> Result<G, GraphIoError>
+where
+    G: GraphMut<NW, EW> + Default,
+    NW: DeserializeOwned,
+    EW: DeserializeOwned,
+    R: Read,
+{
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut parser = DotParser {
+        tokens: tokenize(&text)?,
+        position: 0,
+        directed: true,
+        strict: false,
+        scopes: vec![Scope::default()],
+        nodes: vec![],
+        node_positions: HashMap::new(),
+        edges: vec![],
+        edge_positions: HashMap::new(),
+    };
+    parser.parse_graph()?;
+
+    let mut graph = G::default();
+    if !graph.supports_edge_direction(parser.directed) {
+        return Err(GraphIoError::schema(format!(
+            "Expected a {}, found a {}",
+            graph_keyword(graph.is_directed()),
+            graph_keyword(parser.directed)
+        )));
+    }
+    let mut nodes = Vec::with_capacity(parser.nodes.len());
+    for (id, mut attributes) in parser.nodes {
+        attributes
+            .entry("label".to_string())
+            .or_insert_with(|| id.clone());
+        let weight = weight_from_attributes(&attributes).map_err(|e| {
+            GraphIoError::schema(format!("Couldn't deserialize weight of node {id:?}: {e}"))
+        })?;
+        nodes.push(graph.add_node(weight));
+    }
+    for (a, b, attributes) in parser.edges {
+        let weight = weight_from_attributes(&attributes).map_err(|e| {
+            GraphIoError::schema(format!("Couldn't deserialize weight of an edge: {e}"))
+        })?;
+        graph
+            .try_add_edge_with_direction(nodes[a], nodes[b], weight, parser.directed)
+            .expect("The direction of the graph has been checked before.");
+    }
+    Ok(graph)
+}
+
+fn graph_keyword(directed: bool) -> &'static str {
+    if directed {
+        "digraph"
+    } else {
+        "graph"
+    }
+}
+
+/// Deserializes a weight from the attributes of a node or edge, falling back to its label.
+fn weight_from_attributes<W: DeserializeOwned>(
+    attributes: &DotAttributes,
+) -> Result<W, de::value::Error> {
+    let fields: Vec<_> = attributes
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    let fallback = match attributes.get("label") {
+        Some(label) => Some(label.as_str()),
+        None if attributes.is_empty() => Some(""),
+        None => None,
+    };
+    weight_from_text(&fields, fallback)
+}
+
+/// Tokens of the DOT language.
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// An identifier, number or string.
+    Id {
+        text: String,
+        quoted: bool,
+    },
+    /// An edge operator, `->` if directed and `--` otherwise.
+    EdgeOp {
+        directed: bool,
+    },
+    Symbol(char),
+    Eof,
+}
+
+/// Position of a token in the input, as line and column, both starting at 1.
+type Position = (usize, usize);
+
+/// Splits `text` into tokens, skipping whitespace and comments.
+fn tokenize(text: &str) -> Result<Vec<(Token, Position)>, GraphIoError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+    let (mut i, mut line, mut line_start) = (0, 1, 0);
+    // Advances past the character at position i, keeping track of lines.
+    macro_rules! advance {
+        () => {{
+            if chars[i] == '\n' {
+                line += 1;
+                line_start = i + 1;
+            }
+            i += 1;
+        }};
+    }
+    let is_id_char = |c: char| c.is_alphanumeric() || c == '_' || !c.is_ascii();
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = (line, i - line_start + 1);
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace() {
+            advance!();
+        } else if (c == '#' && chars[line_start..i].iter().all(|c| c.is_whitespace()))
+            || (c == '/' && next == Some('/'))
+        {
+            while i < chars.len() && chars[i] != '\n' {
+                advance!();
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                advance!();
+            }
+            if i >= chars.len() {
+                return Err(syntax_error(start, "Unterminated comment"));
+            }
+            i += 2;
+        } else if c == '"' {
+            let mut text = String::new();
+            advance!();
+            loop {
+                match chars.get(i) {
+                    None => return Err(syntax_error(start, "Unterminated string")),
+                    Some('"') => break,
+                    Some('\\') if matches!(chars.get(i + 1), Some('"') | Some('\\')) => {
+                        text.push(chars[i + 1]);
+                        i += 2;
+                    }
+                    // Line continuation.
+                    Some('\\') if chars.get(i + 1) == Some(&'\n') => {
+                        advance!();
+                        advance!();
+                    }
+                    Some(c) => {
+                        text.push(*c);
+                        advance!();
+                    }
+                }
+            }
+            advance!();
+            tokens.push((Token::Id { text, quoted: true }, start));
+        } else if c == '<' {
+            // HTML string, which may contain nested angle brackets.
+            let mut depth = 0;
+            let mut text = String::new();
+            loop {
+                match chars.get(i) {
+                    None => return Err(syntax_error(start, "Unterminated HTML string")),
+                    Some('<') => depth += 1,
+                    Some('>') => depth -= 1,
+                    _ => {}
+                }
+                if depth > 1 || (depth == 1 && chars[i] != '<') {
+                    text.push(chars[i]);
+                }
+                advance!();
+                if depth == 0 {
+                    break;
+                }
+            }
+            tokens.push((Token::Id { text, quoted: true }, start));
+        } else if c == '-' && matches!(next, Some('>') | Some('-')) {
+            i += 2;
+            let directed = next == Some('>');
+            tokens.push((Token::EdgeOp { directed }, start));
+        } else if c == '-' || c == '.' || c.is_ascii_digit() {
+            let mut text = c.to_string();
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                text.push(chars[i]);
+                i += 1;
+            }
+            if text == "-" || text.matches('.').count() > 1 {
+                return Err(syntax_error(start, format!("Invalid number {text:?}")));
+            }
+            tokens.push((
+                Token::Id {
+                    text,
+                    quoted: false,
+                },
+                start,
+            ));
+        } else if is_id_char(c) {
+            let mut text = String::new();
+            while i < chars.len() && is_id_char(chars[i]) {
+                text.push(chars[i]);
+                i += 1;
+            }
+            tokens.push((
+                Token::Id {
+                    text,
+                    quoted: false,
+                },
+                start,
+            ));
+        } else if "{}[]=;,:+".contains(c) {
+            i += 1;
+            tokens.push((Token::Symbol(c), start));
+        } else {
+            return Err(syntax_error(start, format!("Unexpected character {c:?}")));
+        }
+    }
+    tokens.push((Token::Eof, (line, i - line_start + 1)));
+    Ok(tokens)
+}
+
+fn syntax_error(position: Position, message: impl Into<String>) -> GraphIoError {
+    GraphIoError::Syntax {
+        line: position.0 as u64,
+        column: Some(position.1 as u64),
+        message: message.into(),
+    }
+}
+
+/// Default attributes of a graph or subgraph.
+#[derive(Clone, Default)]
+struct Scope {
+    node_defaults: DotAttributes,
+    edge_defaults: DotAttributes,
+}
+
+/// State of [read_dot], collecting nodes and edges along with their attributes.
+struct DotParser {
+    tokens: Vec<(Token, Position)>,
+    position: usize,
+    directed: bool,
+    strict: bool,
+    /// Default attributes of the enclosing subgraphs, the innermost last.
+    scopes: Vec<Scope>,
+    /// IDs and attributes of all nodes, in order of their first appearance.
+    nodes: Vec<(String, DotAttributes)>,
+    /// Position of each node in `nodes`, by ID.
+    node_positions: HashMap<String, usize>,
+    /// Positions of the connected nodes in `nodes`, and attributes of all edges.
+    edges: Vec<(usize, usize, DotAttributes)>,
+    /// Position of the first edge between two nodes in `edges`, only used for strict graphs.
+    edge_positions: HashMap<(usize, usize), usize>,
+}
+
+impl DotParser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.position].0.clone();
+        if token != Token::Eof {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn error(&self, expected: &str) -> GraphIoError {
+        let (token, position) = &self.tokens[self.position];
+        let found = match token {
+            Token::Id { text, .. } => format!("{text:?}"),
+            Token::EdgeOp { directed: true } => "\"->\"".to_string(),
+            Token::EdgeOp { directed: false } => "\"--\"".to_string(),
+            Token::Symbol(c) => format!("{:?}", c.to_string()),
+            Token::Eof => "end of file".to_string(),
+        };
+        syntax_error(*position, format!("Expected {expected}, found {found}"))
+    }
+
+    /// Consumes the symbol `c` if it is next.
+    fn accept(&mut self, c: char) -> bool {
+        let found = *self.peek() == Token::Symbol(c);
+        if found {
+            self.next();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), GraphIoError> {
+        if self.accept(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("{:?}", c.to_string())))
+        }
+    }
+
+    /// Returns whether the next token is the keyword `keyword`. Keywords are case insensitive.
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Id { text, quoted: false } if text.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Consumes an ID, joining quoted strings concatenated with `+`.
+    fn id(&mut self) -> Result<String, GraphIoError> {
+        let Token::Id { mut text, quoted } = self.peek().clone() else {
+            return Err(self.error("an ID"));
+        };
+        self.next();
+        while quoted && *self.peek() == Token::Symbol('+') {
+            self.next();
+            match self.next() {
+                Token::Id {
+                    text: more,
+                    quoted: true,
+                } => text.push_str(&more),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("a quoted string"));
+                }
+            }
+        }
+        Ok(text)
+    }
+
+    fn parse_graph(&mut self) -> Result<(), GraphIoError> {
+        if self.at_keyword("strict") {
+            self.next();
+            self.strict = true;
+        }
+        if self.at_keyword("digraph") {
+            self.directed = true;
+        } else if self.at_keyword("graph") {
+            self.directed = false;
+        } else {
+            return Err(self.error("\"graph\" or \"digraph\""));
+        }
+        self.next();
+        if matches!(self.peek(), Token::Id { .. }) {
+            self.id()?;
+        }
+        self.expect('{')?;
+        self.parse_statements()?;
+        Ok(())
+    }
+
+    /// Parses statements up to and including the closing brace of the current (sub)graph.
+    ///
+    /// Returns the positions of all nodes appearing in it.
+    fn parse_statements(&mut self) -> Result<Vec<usize>, GraphIoError> {
+        let mut nodes = vec![];
+        while !self.accept('}') {
+            if self.at_keyword("graph") || self.at_keyword("node") || self.at_keyword("edge") {
+                let Token::Id { text, .. } = self.next() else {
+                    unreachable!()
+                };
+                let attributes = self.parse_attributes()?;
+                let scope = self.scopes.last_mut().unwrap();
+                match text.to_lowercase().as_str() {
+                    "node" => scope.node_defaults.extend(attributes),
+                    "edge" => scope.edge_defaults.extend(attributes),
+                    // Graph attributes don't belong to any node or edge.
+                    _ => {}
+                }
+            } else if matches!(self.peek(), Token::Id { .. })
+                && self.tokens[self.position + 1].0 == Token::Symbol('=')
+            {
+                // Graph attribute.
+                self.id()?;
+                self.next();
+                self.id()?;
+            } else {
+                nodes.extend(self.parse_node_or_edge_statement()?);
+            }
+            if !self.accept(';') {
+                self.accept(',');
+            }
+        }
+        nodes.sort();
+        nodes.dedup();
+        Ok(nodes)
+    }
+
+    /// Parses a node statement, an edge statement or a subgraph,
+    /// and returns the positions of the nodes appearing in it.
+    fn parse_node_or_edge_statement(&mut self) -> Result<Vec<usize>, GraphIoError> {
+        let mut operands = vec![self.parse_operand()?];
+        while let Token::EdgeOp { directed } = *self.peek() {
+            if directed != self.directed {
+                return Err(self.error(if self.directed { "\"->\"" } else { "\"--\"" }));
+            }
+            self.next();
+            operands.push(self.parse_operand()?);
+        }
+        let attributes = if *self.peek() == Token::Symbol('[') {
+            self.parse_attributes()?
+        } else {
+            DotAttributes::new()
+        };
+
+        if operands.len() == 1 {
+            // Node statements consist of a single node, subgraphs don't take attributes.
+            if let [n] = operands[0][..] {
+                self.nodes[n].1.extend(attributes);
+            }
+        } else {
+            let mut edge_attributes = self.scopes.last().unwrap().edge_defaults.clone();
+            edge_attributes.extend(attributes);
+            for pair in operands.windows(2) {
+                for a in &pair[0] {
+                    for b in &pair[1] {
+                        self.add_edge(*a, *b, edge_attributes.clone());
+                    }
+                }
+            }
+        }
+        Ok(operands.concat())
+    }
+
+    /// Parses a node ID or subgraph as part of a statement, and returns the positions of its nodes.
+    fn parse_operand(&mut self) -> Result<Vec<usize>, GraphIoError> {
+        if self.at_keyword("subgraph") || *self.peek() == Token::Symbol('{') {
+            if self.at_keyword("subgraph") {
+                self.next();
+                if matches!(self.peek(), Token::Id { .. }) {
+                    self.id()?;
+                }
+            }
+            self.expect('{')?;
+            let scope = self.scopes.last().unwrap().clone();
+            self.scopes.push(scope);
+            let nodes = self.parse_statements();
+            self.scopes.pop();
+            nodes
+        } else {
+            let id = self.id()?;
+            // Ports only affect the drawing of edges.
+            if self.accept(':') {
+                self.id()?;
+                if self.accept(':') {
+                    self.id()?;
+                }
+            }
+            Ok(vec![self.node(id)])
+        }
+    }
+
+    /// Parses one or more attribute lists.
+    fn parse_attributes(&mut self) -> Result<DotAttributes, GraphIoError> {
+        let mut attributes = DotAttributes::new();
+        self.expect('[')?;
+        loop {
+            if self.accept(']') {
+                if *self.peek() != Token::Symbol('[') {
+                    return Ok(attributes);
+                }
+                self.next();
+                continue;
+            }
+            let name = self.id()?;
+            self.expect('=')?;
+            let value = self.id()?;
+            attributes.insert(name, value);
+            if !self.accept(';') {
+                self.accept(',');
+            }
+        }
+    }
+
+    /// Returns the position of the node with the given ID, adding it if it doesn't exist yet.
+    fn node(&mut self, id: String) -> usize {
+        if let Some(position) = self.node_positions.get(&id) {
+            return *position;
+        }
+        let attributes = self.scopes.last().unwrap().node_defaults.clone();
+        self.node_positions.insert(id.clone(), self.nodes.len());
+        self.nodes.push((id, attributes));
+        self.nodes.len() - 1
+    }
+
+    fn add_edge(&mut self, a: usize, b: usize, attributes: DotAttributes) {
+        if self.strict {
+            let key = if self.directed {
+                (a, b)
+            } else {
+                (a.min(b), a.max(b))
+            };
+            if let Some(position) = self.edge_positions.get(&key) {
+                self.edges[*position].2.extend(attributes);
+                return;
+            }
+            self.edge_positions.insert(key, self.edges.len());
+        }
+        self.edges.push((a, b, attributes));
+    }
+}
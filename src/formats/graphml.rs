@@ -0,0 +1,728 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{self, BufReader, Read, Write},
+};
+
+use quick_xml::{
+    escape::partial_escape,
+    events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Number, Value};
+
+use crate::graph::{Graph, GraphIoError, GraphMut};
+
+use super::{direction_name, to_values};
+
+/// Namespace of GraphML elements.
+const GRAPHML_NAMESPACE: &str = "http://graphml.graphdrawing.org/xmlns";
+
+/// Name of the data key holding weights that don't serialize to a struct or map.
+const WEIGHT_KEY: &str = "weight";
+
+/// Description marking data keys whose values are stored as JSON text.
+const JSON_DESCRIPTION: &str = "json";
+
+/// Value types of GraphML data keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyType {
+    Boolean,
+    Long,
+    Double,
+    String,
+}
+
+impl KeyType {
+    /// Returns the name used in the `attr.type` attribute.
+    fn name(self) -> &'static str {
+        match self {
+            KeyType::Boolean => "boolean",
+            KeyType::Long => "long",
+            KeyType::Double => "double",
+            KeyType::String => "string",
+        }
+    }
+
+    /// Parses the value of an `attr.type` attribute.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "boolean" => Some(KeyType::Boolean),
+            "int" | "long" => Some(KeyType::Long),
+            "float" | "double" => Some(KeyType::Double),
+            "string" => Some(KeyType::String),
+            _ => None,
+        }
+    }
+}
+
+/// A GraphML data key, storing one field of node or edge weights.
+struct Key {
+    /// Name of the field.
+    name: String,
+    value_type: KeyType,
+    /// Whether values are stored as JSON text. This is used for nested values,
+    /// and for fields holding values of different types.
+    json: bool,
+    /// Value of elements without data for this key.
+    default: Option<Value>,
+}
+
+impl Key {
+    /// Creates a key able to store `value`, or None if `value` is null and thus left out.
+    fn for_value(name: &str, value: &Value) -> Option<Self> {
+        let (value_type, json) = match value {
+            Value::Null => return None,
+            Value::Bool(_) => (KeyType::Boolean, false),
+            Value::Number(n) if n.is_i64() => (KeyType::Long, false),
+            Value::Number(n) if n.is_f64() => (KeyType::Double, false),
+            Value::String(_) => (KeyType::String, false),
+            // Large unsigned numbers, arrays and maps.
+            _ => (KeyType::String, true),
+        };
+        Some(Key {
+            name: name.to_string(),
+            value_type,
+            json,
+            default: None,
+        })
+    }
+
+    /// Widens this key so that it can also store `value`.
+    fn merge(&mut self, value: &Value) {
+        let Some(other) = Key::for_value(&self.name, value) else {
+            return;
+        };
+        if self.json || (self.value_type == other.value_type && !other.json) {
+            return;
+        }
+        match (self.value_type, other.value_type) {
+            (KeyType::Long, KeyType::Double) | (KeyType::Double, KeyType::Long) => {
+                self.value_type = KeyType::Double;
+            }
+            _ => {
+                self.value_type = KeyType::String;
+                self.json = true;
+            }
+        }
+    }
+
+    /// Formats `value` as the text of a data element.
+    fn format(&self, value: &Value) -> String {
+        match value {
+            _ if self.json => value.to_string(),
+            Value::String(s) => s.clone(),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Parses the text of a data element, returning None if it isn't a valid value of this key.
+    fn parse(&self, text: &str) -> Option<Value> {
+        if self.json {
+            return serde_json::from_str(text).ok();
+        }
+        match self.value_type {
+            KeyType::Boolean => match text.trim().to_lowercase().as_str() {
+                "true" | "1" => Some(Value::Bool(true)),
+                "false" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            KeyType::Long => text.trim().parse::<i64>().ok().map(Value::from),
+            KeyType::Double => text
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number),
+            KeyType::String => Some(Value::String(text.to_string())),
+        }
+    }
+}
+
+/// The data keys of all nodes or all edges of a graph.
+struct Keys {
+    keys: Vec<Key>,
+    /// Position of each key in `keys`, by field name.
+    positions: HashMap<String, usize>,
+    /// Whether weights are split into one key per field, or stored under [WEIGHT_KEY].
+    by_field: bool,
+}
+
+impl Keys {
+    /// Chooses the data keys for the given serialized weights.
+    ///
+    /// If all weights serialize to maps, e.g. structs, each field gets its own key.
+    /// Otherwise the whole weights are stored under a single key.
+    fn infer(values: &[Value]) -> Self {
+        let mut keys = Keys {
+            keys: vec![],
+            positions: HashMap::new(),
+            by_field: values.iter().all(|v| v.is_object() || v.is_null()),
+        };
+        for value in values {
+            for (name, field) in keys.fields(value) {
+                match keys.positions.get(name) {
+                    Some(position) => keys.keys[*position].merge(field),
+                    None => {
+                        if let Some(key) = Key::for_value(name, field) {
+                            keys.positions.insert(name.to_string(), keys.keys.len());
+                            keys.keys.push(key);
+                        }
+                    }
+                }
+            }
+        }
+        keys
+    }
+
+    /// Returns the fields of a serialized weight, along with their names.
+    fn fields<'a>(&self, value: &'a Value) -> Vec<(&'a str, &'a Value)> {
+        if self.by_field {
+            value
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, field)| (name.as_str(), field))
+                .collect()
+        } else {
+            vec![(WEIGHT_KEY, value)]
+        }
+    }
+}
+
+/// Writes `g` to `writer` in GraphML format.
+///
+/// Node and edge weights are serialized through serde. Weights that serialize to maps,
+/// like structs, are stored with one GraphML data key per field. Other weights are stored
+/// under a single data key named `weight`. Nested values are stored as JSON text.
+///
+/// Nodes are given the ids `n0`, `n1`, ... in the order in which `g` yields them.
+/// Edges whose direction differs from the direction of the graph get a `directed` attribute.
+pub fn write_graphml<G, NW, EW, W>(g: &G, writer: W) -> Result<(), GraphIoError>
+where
+    G: Graph<NW, EW>,
+    NW: Serialize,
+    EW: Serialize,
+    W: Write,
+{
+    let nodes: Vec<_> = g.nodes().collect();
+    let node_ids: HashMap<_, _> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (*n, format!("n{i}")))
+        .collect();
+    let node_values = to_values(nodes.iter().map(|n| g.node_weight(*n)))?;
+    let edges: Vec<_> = g.edges().collect();
+    let edge_values = to_values(edges.iter().map(|e| g.edge_weight(*e)))?;
+    let node_keys = Keys::infer(&node_values);
+    let edge_keys = Keys::infer(&edge_values);
+
+    let mut xml = Writer::new_with_indent(writer, b' ', 2);
+    xml.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    xml.write_event(Event::Start(
+        BytesStart::new("graphml").with_attributes([("xmlns", GRAPHML_NAMESPACE)]),
+    ))?;
+    // Node keys are numbered first, edge keys continue after them.
+    let key_ids = |domain: &str, i: usize| match domain {
+        "node" => format!("d{i}"),
+        _ => format!("d{}", node_keys.keys.len() + i),
+    };
+    for (domain, keys) in [("node", &node_keys), ("edge", &edge_keys)] {
+        for (i, key) in keys.keys.iter().enumerate() {
+            let id = key_ids(domain, i);
+            let start = BytesStart::new("key").with_attributes([
+                ("id", id.as_str()),
+                ("for", domain),
+                ("attr.name", key.name.as_str()),
+                ("attr.type", key.value_type.name()),
+            ]);
+            if key.json {
+                xml.write_event(Event::Start(start))?;
+                write_text_element(&mut xml, "desc", JSON_DESCRIPTION)?;
+                xml.write_event(Event::End(BytesEnd::new("key")))?;
+            } else {
+                xml.write_event(Event::Empty(start))?;
+            }
+        }
+    }
+
+    let directed = g.is_directed();
+    let edgedefault = if directed { "directed" } else { "undirected" };
+    xml.write_event(Event::Start(
+        BytesStart::new("graph").with_attributes([("id", "G"), ("edgedefault", edgedefault)]),
+    ))?;
+    for (n, value) in nodes.iter().zip(&node_values) {
+        let start = BytesStart::new("node").with_attributes([("id", node_ids[n].as_str())]);
+        write_element(&mut xml, start, &node_keys, value, |i| key_ids("node", i))?;
+    }
+    for (i, (e, value)) in edges.iter().zip(&edge_values).enumerate() {
+        let (a, b) = g.adjacent_nodes(*e);
+        let id = format!("e{i}");
+        let mut start = BytesStart::new("edge").with_attributes([
+            ("id", id.as_str()),
+            ("source", node_ids[&a].as_str()),
+            ("target", node_ids[&b].as_str()),
+        ]);
+        if g.is_directed_edge(*e) != directed {
+            start.push_attribute(("directed", if directed { "false" } else { "true" }));
+        }
+        write_element(&mut xml, start, &edge_keys, value, |i| key_ids("edge", i))?;
+    }
+    xml.write_event(Event::End(BytesEnd::new("graph")))?;
+    xml.write_event(Event::End(BytesEnd::new("graphml")))?;
+    Ok(xml.into_inner().flush()?)
+}
+
+/// Writes a node or edge element, along with a data element for each field of `value`.
+fn write_element<W: Write>(
+    xml: &mut Writer<W>,
+    start: BytesStart,
+    keys: &Keys,
+    value: &Value,
+    key_id: impl Fn(usize) -> String,
+) -> io::Result<()> {
+    let fields: Vec<_> = keys
+        .fields(value)
+        .into_iter()
+        .filter(|(_, field)| !field.is_null())
+        .collect();
+    if fields.is_empty() {
+        return xml.write_event(Event::Empty(start));
+    }
+    let end = start.to_end().into_owned();
+    xml.write_event(Event::Start(start))?;
+    for (name, field) in fields {
+        let position = keys.positions[name];
+        let text = keys.keys[position].format(field);
+        let data = BytesStart::new("data").with_attributes([("key", key_id(position).as_str())]);
+        xml.write_event(Event::Start(data))?;
+        // Quotes don't need to be escaped in text, which keeps JSON values readable.
+        xml.write_event(Event::Text(BytesText::from_escaped(partial_escape(text))))?;
+        xml.write_event(Event::End(BytesEnd::new("data")))?;
+    }
+    xml.write_event(Event::End(end))
+}
+
+/// Writes an element that only contains `text`.
+fn write_text_element<W: Write>(xml: &mut Writer<W>, name: &str, text: &str) -> io::Result<()> {
+    xml.write_event(Event::Start(BytesStart::new(name)))?;
+    xml.write_event(Event::Text(BytesText::new(text)))?;
+    xml.write_event(Event::End(BytesEnd::new(name)))
+}
+
+/// Reads a graph in GraphML format from `reader`.
+///
+/// Weights are deserialized through serde from the data of each node and edge, with fields named by
+/// the `attr.name` of their data keys. Defaults declared by the keys are used for missing data.
+/// If a weight can't be deserialized from its fields, it is deserialized from the value of
+/// the `weight` key, so files written by [write_graphml] can be read back.
+///
+/// `G` must be able to hold edges of the direction of the graph and of each of its edges,
+/// see [crate::graph::GraphMut::supports_edge_direction]. A [crate::mixed_graph::MixedGraph] can hold all of them.
+/// Only the first graph of the file is read. Hyperedges and nested graphs are not supported.
+///
+/// Errors report the line of the element causing them, but no column.
+pub fn read_graphml<G, NW, EW, R>(reader: R) -> Result<G, GraphIoError>
+where
+    G: GraphMut<NW, EW> + Default,
+    NW: DeserializeOwned,
+    EW: DeserializeOwned,
+    R: Read,
+{
+    let mut xml = Reader::from_reader(BufReader::new(LineCounter {
+        inner: reader,
+        position: 0,
+        lines: 0,
+        line_starts: VecDeque::from([0]),
+    }));
+    xml.config_mut().expand_empty_elements = true;
+    let mut parser = GraphMlParser {
+        xml,
+        buffer: vec![],
+        line: 1,
+        keys: HashMap::new(),
+    };
+    parser.read_document()
+}
+
+/// Wraps the input of [read_graphml] to find the lines of byte positions in it.
+struct LineCounter<R> {
+    inner: R,
+    /// Number of bytes read so far.
+    position: u64,
+    /// Number of lines before the first one in `line_starts`.
+    lines: u64,
+    /// Byte positions at which lines start, from the line of the last lookup on.
+    line_starts: VecDeque<u64>,
+}
+
+impl<R: Read> Read for LineCounter<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buffer)?;
+        for (i, byte) in buffer[..len].iter().enumerate() {
+            if *byte == b'\n' {
+                self.line_starts.push_back(self.position + i as u64 + 1);
+            }
+        }
+        self.position += len as u64;
+        Ok(len)
+    }
+}
+
+impl<R> LineCounter<R> {
+    /// Returns the line of the byte at `position`, starting at 1.
+    ///
+    /// Positions must be looked up in increasing order, so that the starts of earlier lines can be dropped.
+    fn line(&mut self, position: u64) -> u64 {
+        let passed = self.line_starts.partition_point(|start| *start <= position);
+        let line = self.lines + passed as u64;
+        let dropped = passed.saturating_sub(1);
+        self.line_starts.drain(..dropped);
+        self.lines += dropped as u64;
+        line
+    }
+}
+
+/// State of [read_graphml].
+struct GraphMlParser<R> {
+    xml: Reader<BufReader<LineCounter<R>>>,
+    buffer: Vec<u8>,
+    /// Line at which the last XML event started.
+    line: u64,
+    /// Declared data keys by their id.
+    keys: HashMap<String, (String, Key)>,
+}
+
+impl<R: Read> GraphMlParser<R> {
+    /// Reads the next XML event.
+    fn next(&mut self) -> Result<Event<'static>, GraphIoError> {
+        self.buffer.clear();
+        let start = self.xml.buffer_position();
+        let event = self
+            .xml
+            .read_event_into(&mut self.buffer)
+            .map(Event::into_owned);
+        match event {
+            Ok(event) => {
+                self.line = self.line_at(start);
+                Ok(event)
+            }
+            Err(e) => Err(self.xml_error(e)),
+        }
+    }
+
+    /// Skips the element started by `start`, including all its children.
+    fn skip(&mut self, start: &BytesStart) -> Result<(), GraphIoError> {
+        self.buffer.clear();
+        match self.xml.read_to_end_into(start.name(), &mut self.buffer) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(self.xml_error(e)),
+        }
+    }
+
+    /// Returns the line of the byte at `position`.
+    fn line_at(&mut self, position: u64) -> u64 {
+        self.xml.get_mut().get_mut().line(position)
+    }
+
+    /// Converts errors of the XML parser, reporting the line at which the parser found them.
+    fn xml_error(&mut self, e: quick_xml::Error) -> GraphIoError {
+        match e {
+            quick_xml::Error::Io(e) => GraphIoError::Io(io::Error::new(e.kind(), e.to_string())),
+            e => {
+                let position = self.xml.error_position().max(self.xml.buffer_position());
+                GraphIoError::Syntax {
+                    line: self.line_at(position),
+                    column: None,
+                    message: e.to_string(),
+                }
+            }
+        }
+    }
+
+    /// Creates the error returned for malformed XML found in the last event.
+    fn syntax_error(&self, message: impl Into<String>) -> GraphIoError {
+        GraphIoError::Syntax {
+            line: self.line,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Creates the error returned for valid XML that doesn't describe a supported graph,
+    /// found in the last event.
+    fn schema_error(&self, message: impl Into<String>) -> GraphIoError {
+        GraphIoError::Schema {
+            line: Some(self.line),
+            message: message.into(),
+        }
+    }
+
+    /// Returns the unescaped value of attribute `name`, if present.
+    fn attribute(&self, start: &BytesStart, name: &str) -> Result<Option<String>, GraphIoError> {
+        let attribute = start
+            .try_get_attribute(name)
+            .map_err(|e| self.syntax_error(e.to_string()))?;
+        match attribute {
+            Some(a) => match a.unescape_value() {
+                Ok(value) => Ok(Some(value.into_owned())),
+                Err(e) => Err(self.syntax_error(e.to_string())),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the unescaped value of attribute `name`, failing if it is missing.
+    fn required_attribute(&self, start: &BytesStart, name: &str) -> Result<String, GraphIoError> {
+        self.attribute(start, name)?.ok_or_else(|| {
+            self.schema_error(format!(
+                "Missing attribute {name:?} of element {:?}",
+                String::from_utf8_lossy(start.name().as_ref())
+            ))
+        })
+    }
+
+    /// Reads the text content of the current element, up to its end.
+    fn read_text(&mut self) -> Result<String, GraphIoError> {
+        let mut text = String::new();
+        loop {
+            match self.next()? {
+                Event::Text(t) => match t.unescape() {
+                    Ok(t) => text.push_str(&t),
+                    Err(e) => return Err(self.syntax_error(e.to_string())),
+                },
+                Event::CData(t) => text.push_str(&String::from_utf8_lossy(&t)),
+                Event::Start(e) => self.skip(&e)?,
+                Event::End(_) => return Ok(text),
+                Event::Eof => return Err(self.syntax_error("Unexpected end of file")),
+                _ => {}
+            }
+        }
+    }
+
+    fn read_document<G, NW, EW>(&mut self) -> Result<G, GraphIoError>
+    where
+        G: GraphMut<NW, EW> + Default,
+        NW: DeserializeOwned,
+        EW: DeserializeOwned,
+    {
+        loop {
+            match self.next()? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"graphml" => {}
+                    b"key" => self.read_key(&e)?,
+                    b"graph" => return self.read_graph(&e),
+                    _ => self.skip(&e)?,
+                },
+                Event::Eof => {
+                    return Err(GraphIoError::schema("GraphML file doesn't contain a graph"))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads the declaration of a data key.
+    fn read_key(&mut self, start: &BytesStart) -> Result<(), GraphIoError> {
+        let line = self.line;
+        let id = self.required_attribute(start, "id")?;
+        let domain = self
+            .attribute(start, "for")?
+            .unwrap_or_else(|| "all".to_string());
+        let name = self
+            .attribute(start, "attr.name")?
+            .unwrap_or_else(|| id.clone());
+        let type_name = self
+            .attribute(start, "attr.type")?
+            .unwrap_or_else(|| "string".to_string());
+        let value_type = KeyType::from_name(&type_name).ok_or_else(|| {
+            self.schema_error(format!("Unsupported type {type_name:?} of key {id:?}"))
+        })?;
+        let mut key = Key {
+            name,
+            value_type,
+            json: false,
+            default: None,
+        };
+
+        let mut default = None;
+        loop {
+            match self.next()? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"desc" => key.json = self.read_text()?.trim() == JSON_DESCRIPTION,
+                    b"default" => default = Some(self.read_text()?),
+                    _ => self.skip(&e)?,
+                },
+                Event::End(_) => break,
+                Event::Eof => return Err(self.syntax_error("Unexpected end of file")),
+                _ => {}
+            }
+        }
+        if let Some(text) = default {
+            key.default = Some(key.parse(&text).ok_or_else(|| GraphIoError::Schema {
+                line: Some(line),
+                message: format!("Invalid default value {text:?} of key {id:?}"),
+            })?);
+        }
+        self.keys.insert(id, (domain, key));
+        Ok(())
+    }
+
+    /// Reads the nodes and edges of a graph element.
+    fn read_graph<G, NW, EW>(&mut self, start: &BytesStart) -> Result<G, GraphIoError>
+    where
+        G: GraphMut<NW, EW> + Default,
+        NW: DeserializeOwned,
+        EW: DeserializeOwned,
+    {
+        let directed = match self.attribute(start, "edgedefault")?.as_deref() {
+            Some("directed") | None => true,
+            Some("undirected") => false,
+            Some(other) => return Err(self.schema_error(format!("Invalid edgedefault {other:?}"))),
+        };
+        let mut graph = G::default();
+        if !graph.supports_edge_direction(directed) {
+            return Err(self.schema_error(format!(
+                "Expected a {} graph, found a {} one",
+                direction_name(graph.is_directed()),
+                direction_name(directed)
+            )));
+        }
+
+        let mut nodes = HashMap::new();
+        // Edges may refer to nodes declared after them, so they are added at the end.
+        let mut edges = vec![];
+        loop {
+            match self.next()? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"node" => {
+                        let line = self.line;
+                        let id = self.required_attribute(&e, "id")?;
+                        if nodes.contains_key(&id) {
+                            return Err(self.schema_error(format!("Duplicate node id {id:?}")));
+                        }
+                        let weight = self.read_weight("node", &id, line)?;
+                        nodes.insert(id, graph.add_node(weight));
+                    }
+                    b"edge" => {
+                        let line = self.line;
+                        let source = self.required_attribute(&e, "source")?;
+                        let target = self.required_attribute(&e, "target")?;
+                        let id = self
+                            .attribute(&e, "id")?
+                            .unwrap_or_else(|| format!("from {source:?} to {target:?}"));
+                        let edge_directed = match self.attribute(&e, "directed")?.as_deref() {
+                            Some("true") => true,
+                            Some("false") => false,
+                            _ => directed,
+                        };
+                        let weight = self.read_weight("edge", &id, line)?;
+                        edges.push((line, id, source, target, edge_directed, weight));
+                    }
+                    b"hyperedge" => return Err(self.schema_error("Hyperedges are not supported")),
+                    _ => self.skip(&e)?,
+                },
+                Event::End(_) => break,
+                Event::Eof => return Err(self.syntax_error("Unexpected end of file")),
+                _ => {}
+            }
+        }
+
+        for (line, id, source, target, edge_directed, weight) in edges {
+            let endpoint = |node: &String| {
+                nodes
+                    .get(node)
+                    .copied()
+                    .ok_or_else(|| GraphIoError::DanglingEdge {
+                        line: Some(line),
+                        node: node.clone(),
+                    })
+            };
+            let (a, b) = (endpoint(&source)?, endpoint(&target)?);
+            if graph
+                .try_add_edge_with_direction(a, b, weight, edge_directed)
+                .is_none()
+            {
+                return Err(GraphIoError::Schema {
+                    line: Some(line),
+                    message: format!(
+                        "Edge {id:?} is {}, but the graph only supports {} edges",
+                        direction_name(edge_directed),
+                        direction_name(directed)
+                    ),
+                });
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Reads the data elements of the current node or edge, and deserializes its weight from them.
+    ///
+    /// `line` is the line at which the node or edge starts.
+    fn read_weight<W: DeserializeOwned>(
+        &mut self,
+        domain: &str,
+        id: &str,
+        line: u64,
+    ) -> Result<W, GraphIoError> {
+        let mut fields = Map::new();
+        loop {
+            match self.next()? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"data" => {
+                        let data_line = self.line;
+                        let key_id = self.required_attribute(&e, "key")?;
+                        let text = self.read_text()?;
+                        let data_error = |message| GraphIoError::Schema {
+                            line: Some(data_line),
+                            message,
+                        };
+                        let (_, key) = self.keys.get(&key_id).ok_or_else(|| {
+                            data_error(format!(
+                                "Data of {domain} {id:?} uses undeclared key {key_id:?}"
+                            ))
+                        })?;
+                        let value = key.parse(&text).ok_or_else(|| {
+                            data_error(format!(
+                                "Invalid value {text:?} of key {key_id:?} in {domain} {id:?}"
+                            ))
+                        })?;
+                        fields.insert(key.name.clone(), value);
+                    }
+                    b"graph" => return Err(self.schema_error("Nested graphs are not supported")),
+                    _ => self.skip(&e)?,
+                },
+                Event::End(_) => break,
+                Event::Eof => return Err(self.syntax_error("Unexpected end of file")),
+                _ => {}
+            }
+        }
+
+        for (key_domain, key) in self.keys.values() {
+            if let Some(default) = &key.default {
+                if (key_domain == domain || key_domain == "all") && !fields.contains_key(&key.name)
+                {
+                    fields.insert(key.name.clone(), default.clone());
+                }
+            }
+        }
+        weight_from_fields(fields).map_err(|e| GraphIoError::Schema {
+            line: Some(line),
+            message: format!("Couldn't deserialize weight of {domain} {id:?}: {e}"),
+        })
+    }
+}
+
+/// Deserializes a weight from the fields read from its data elements.
+fn weight_from_fields<W: DeserializeOwned>(fields: Map<String, Value>) -> serde_json::Result<W> {
+    // Weights that don't serialize to maps are stored under WEIGHT_KEY, or not at all if they are null.
+    let single = match fields.len() {
+        0 => Some(Value::Null),
+        1 => fields.get(WEIGHT_KEY).cloned(),
+        _ => None,
+    };
+    serde_json::from_value(Value::Object(fields)).or_else(|e| match single {
+        Some(value) => serde_json::from_value(value).map_err(|_| e),
+        None => Err(e),
+    })
+}
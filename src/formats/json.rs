@@ -0,0 +1,191 @@
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::graph::{Graph, GraphIoError, GraphMut};
+
+use super::direction_name;
+
+/// A graph in the backend-independent JSON format of [write_json] and [read_json].
+///
+/// The format looks like this, with weights serialized through serde:
+///
+/// ```json
+/// {
+///   "directed": true,
+///   "nodes": [
+///     { "id": 0, "weight": "Ulm" },
+///     { "id": 1, "weight": "Neu-Ulm" }
+///   ],
+///   "edges": [
+///     { "source": 0, "target": 1, "weight": 3 },
+///     { "source": 1, "target": 0, "weight": 4, "directed": false }
+///   ]
+/// }
+/// ```
+///
+/// Node IDs must be unique, but need not be consecutive. The `directed` field of an edge
+/// is only present if its direction differs from the direction of the graph.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonGraph<NodeWeight, EdgeWeight> {
+    /// Whether the edges of the graph are directed.
+    pub directed: bool,
+    /// Nodes of the graph.
+    pub nodes: Vec<JsonNode<NodeWeight>>,
+    /// Edges of the graph.
+    pub edges: Vec<JsonEdge<EdgeWeight>>,
+}
+
+/// A node of a [JsonGraph].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonNode<NodeWeight> {
+    /// ID of the node, unique within the graph.
+    pub id: usize,
+    /// Weight of the node.
+    pub weight: NodeWeight,
+}
+
+/// An edge of a [JsonGraph], referring to its nodes by their IDs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonEdge<EdgeWeight> {
+    /// ID of the source node.
+    pub source: usize,
+    /// ID of the target node.
+    pub target: usize,
+    /// Weight of the edge.
+    pub weight: EdgeWeight,
+    /// Direction of the edge, if it differs from the direction of the graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directed: Option<bool>,
+}
+
+impl<'a, NodeWeight, EdgeWeight> JsonGraph<&'a NodeWeight, &'a EdgeWeight> {
+    /// Creates the JSON representation of `g`, borrowing its weights.
+    ///
+    /// Nodes are given the IDs 0, 1, ... in the order in which `g` yields them.
+    pub fn from_graph<G>(g: &'a G) -> Self
+    where
+        G: Graph<NodeWeight, EdgeWeight>,
+    {
+        let directed = g.is_directed();
+        let ids: HashMap<_, _> = g.nodes().enumerate().map(|(i, n)| (n, i)).collect();
+        JsonGraph {
+            directed,
+            nodes: g
+                .nodes()
+                .map(|n| JsonNode {
+                    id: ids[&n],
+                    weight: g.node_weight(n),
+                })
+                .collect(),
+            edges: g
+                .edges()
+                .map(|e| {
+                    let (a, b) = g.adjacent_nodes(e);
+                    let edge_directed = g.is_directed_edge(e);
+                    JsonEdge {
+                        source: ids[&a],
+                        target: ids[&b],
+                        weight: g.edge_weight(e),
+                        directed: (edge_directed != directed).then_some(edge_directed),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<NodeWeight, EdgeWeight> JsonGraph<NodeWeight, EdgeWeight> {
+    /// Builds a graph of type `G` with the nodes and edges of this JSON graph.
+    ///
+    /// Edges are added with their direction through [GraphMut::try_add_edge_with_direction],
+    /// so a [crate::mixed_graph::MixedGraph] can hold the edges of both directed and undirected graphs.
+    ///
+    /// Fails if `G` can't hold edges of the direction of the graph or of one of its edges
+    /// (see [GraphMut::supports_edge_direction]),
+    /// if node IDs are repeated, or if an edge refers to a node that doesn't exist.
+    pub fn into_graph<G>(self) -> Result<G, GraphIoError>
+    where
+        G: GraphMut<NodeWeight, EdgeWeight> + Default,
+    {
+        let mut graph = G::default();
+        if !graph.supports_edge_direction(self.directed) {
+            return Err(GraphIoError::schema(format!(
+                "Expected a {} graph, found a {} graph",
+                direction_name(graph.is_directed()),
+                direction_name(self.directed)
+            )));
+        }
+        let mut nodes = HashMap::with_capacity(self.nodes.len());
+        for node in self.nodes {
+            if nodes.contains_key(&node.id) {
+                return Err(GraphIoError::schema(format!(
+                    "Duplicate node ID {}",
+                    node.id
+                )));
+            }
+            nodes.insert(node.id, graph.add_node(node.weight));
+        }
+        for (i, edge) in self.edges.into_iter().enumerate() {
+            let endpoint = |id: usize| {
+                nodes
+                    .get(&id)
+                    .copied()
+                    .ok_or_else(|| GraphIoError::DanglingEdge {
+                        line: None,
+                        node: id.to_string(),
+                    })
+            };
+            let (a, b) = (endpoint(edge.source)?, endpoint(edge.target)?);
+            let edge_directed = edge.directed.unwrap_or(self.directed);
+            if graph
+                .try_add_edge_with_direction(a, b, edge.weight, edge_directed)
+                .is_none()
+            {
+                return Err(GraphIoError::schema(format!(
+                    "Edge {i} is {}, but the graph is {}",
+                    direction_name(edge_directed),
+                    direction_name(self.directed)
+                )));
+            }
+        }
+        Ok(graph)
+    }
+}
+
+/// Writes `g` to `writer` in the JSON format described at [JsonGraph].
+///
+/// Unlike [graph::GraphReadWriter::write_graph], the output doesn't depend on the storage backend of `g`,
+/// so it can be read into any other graph implementation with [read_json].
+///
+/// [graph::GraphReadWriter::write_graph]: crate::graph::GraphReadWriter::write_graph
+pub fn write_json<G, NW, EW, W>(g: &G, writer: W) -> Result<(), GraphIoError>
+where
+    G: Graph<NW, EW>,
+    NW: Serialize,
+    EW: Serialize,
+    W: Write,
+{
+    serde_json::to_writer(writer, &JsonGraph::from_graph(g)).map_err(GraphIoError::from)
+}
+
+/// Reads a graph in the JSON format described at [JsonGraph] from `reader`.
+///
+/// The direction of the graph must match the direction of `G`, see [JsonGraph::into_graph].
+/// Any graph, including graphs mixing directed and undirected edges, can be read into a [crate::mixed_graph::MixedGraph].
+///
+/// Backends that can't be modified, like [crate::csr_graph::CsrGraph], are built from a graph read
+/// into another backend, e.g. with [crate::csr_graph::CsrGraph::from_graph].
+pub fn read_json<G, NW, EW, R>(reader: R) -> Result<G, GraphIoError>
+where
+    G: GraphMut<NW, EW> + Default,
+    NW: DeserializeOwned,
+    EW: DeserializeOwned,
+    R: Read,
+{
+    let graph: JsonGraph<NW, EW> = serde_json::from_reader(reader).map_err(GraphIoError::from)?;
+    graph.into_graph()
+}
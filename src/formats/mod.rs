@@ -0,0 +1,65 @@
+//!
+//! This module provides readers and writers for graph file formats used by other tools.
+//!
+//! Unlike [graph::GraphReadWriter], which stores graphs in the internal layout of a storage backend,
+//! these formats are independent of the backend. Writers accept any [graph::Graph] implementation,
+//! readers build any graph implementing [graph::GraphMut] and [Default].
+//! Node and edge weights are converted through serde.
+//! All readers and writers report errors as [graph::GraphIoError], with the position of the problem where it is known.
+//!
+//! * [formats::write_graphml] and [formats::read_graphml] support GraphML, as used by yEd, Gephi and NetworkX.
+//! * [formats::read_dot] reads Graphviz DOT files, e.g. as written by [graph::VizDotGraph::print].
+//! * [formats::write_csv] and [formats::read_csv] support pairs of CSV files listing nodes and edges.
+//! * [formats::write_json] and [formats::read_json] support a documented JSON format, see [formats::JsonGraph].
+//! * [formats::write_binary] and [formats::read_binary] support a compact binary format for large graphs.
+//! * [formats::read_ndjson] and [formats::read_edge_list] load very large files line by line, reporting their progress.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::graph::GraphIoError;
+
+/// GraphML import and export.
+mod graphml;
+pub use graphml::*;
+
+/// Graphviz DOT import.
+mod dot;
+pub use dot::*;
+
+/// CSV node and edge list import and export.
+mod csv;
+pub use self::csv::*;
+
+/// Backend-independent JSON format.
+mod json;
+pub use json::*;
+
+/// Compact binary format.
+mod binary;
+pub use binary::*;
+
+/// Streaming import of large node and edge lists.
+mod streaming;
+pub use streaming::*;
+
+/// Deserializing weights from text fields.
+mod text_fields;
+
+/// Returns how the direction of a graph or edge is described in error messages.
+fn direction_name(directed: bool) -> &'static str {
+    if directed {
+        "directed"
+    } else {
+        "undirected"
+    }
+}
+
+/// Serializes all weights yielded by `weights` into JSON values.
+fn to_values<'a, W: Serialize + 'a>(
+    weights: impl Iterator<Item = &'a W>,
+) -> Result<Vec<Value>, GraphIoError> {
+    weights
+        .map(|w| serde_json::to_value(w).map_err(GraphIoError::from))
+        .collect()
+}
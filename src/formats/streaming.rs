@@ -0,0 +1,218 @@
+use std::{collections::HashMap, io::BufRead};
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::graph::{GraphIoError, GraphMut};
+
+use super::{direction_name, text_fields::weight_from_text, JsonEdge, JsonNode};
+
+/// Number of lines after which the progress callbacks of the streaming readers are called.
+pub const PROGRESS_INTERVAL: u64 = 1 << 16;
+
+/// Progress of a streaming reader, as reported to its callback.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// Number of bytes read so far.
+    pub bytes: u64,
+    /// Number of lines read so far.
+    pub lines: u64,
+    /// Number of nodes added so far.
+    pub nodes: usize,
+    /// Number of edges added so far.
+    pub edges: usize,
+}
+
+/// A line of a newline-delimited JSON file.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonRecord<NodeWeight, EdgeWeight> {
+    Edge(JsonEdge<EdgeWeight>),
+    Node(JsonNode<NodeWeight>),
+}
+
+/// Reads a graph from newline-delimited JSON, where each line holds a single node or edge record.
+///
+/// Records have the same layout as the nodes and edges of a [crate::formats::JsonGraph],
+/// e.g. `{"id": 0, "weight": "Ulm"}` or `{"source": 0, "target": 1, "weight": 3}`.
+/// A node must appear before the edges referring to it. Blank lines are skipped.
+/// Edges whose `directed` field differs from the direction of `G` can only be read if `G` can hold them,
+/// e.g. into a [crate::mixed_graph::MixedGraph].
+///
+/// The graph is built while reading, so apart from the graph itself, only a single line and a map
+/// from node IDs to nodes are kept in memory. `progress` is called every [PROGRESS_INTERVAL] lines
+/// and once at the end.
+pub fn read_ndjson<G, NW, EW, R, F>(reader: R, progress: F) -> Result<G, GraphIoError>
+where
+    G: GraphMut<NW, EW> + Default,
+    NW: DeserializeOwned,
+    EW: DeserializeOwned,
+    R: BufRead,
+    F: FnMut(&LoadProgress),
+{
+    let mut nodes = HashMap::new();
+    read_lines(reader, progress, |graph: &mut G, state, line| {
+        let record = serde_json::from_str(line).map_err(|e| match GraphIoError::from(e) {
+            // serde_json counts the lines of the record, which always has a single one.
+            GraphIoError::Syntax {
+                column, message, ..
+            } => GraphIoError::Syntax {
+                line: state.lines,
+                column,
+                message,
+            },
+            GraphIoError::Schema { message, .. } => GraphIoError::Schema {
+                line: Some(state.lines),
+                message: format!("Expected a node or edge record: {message}"),
+            },
+            e => e,
+        })?;
+        match record {
+            JsonRecord::Node(node) => {
+                if nodes.contains_key(&node.id) {
+                    return Err(GraphIoError::Schema {
+                        line: Some(state.lines),
+                        message: format!("Duplicate node ID {}", node.id),
+                    });
+                }
+                nodes.insert(node.id, graph.add_node(node.weight));
+                state.nodes += 1;
+            }
+            JsonRecord::Edge(edge) => {
+                let endpoint = |id| {
+                    nodes
+                        .get(&id)
+                        .copied()
+                        .ok_or_else(|| GraphIoError::DanglingEdge {
+                            line: Some(state.lines),
+                            node: id.to_string(),
+                        })
+                };
+                let (a, b) = (endpoint(edge.source)?, endpoint(edge.target)?);
+                let directed = graph.is_directed();
+                let edge_directed = edge.directed.unwrap_or(directed);
+                if graph
+                    .try_add_edge_with_direction(a, b, edge.weight, edge_directed)
+                    .is_none()
+                {
+                    return Err(GraphIoError::Schema {
+                        line: Some(state.lines),
+                        message: format!(
+                            "Edge is {}, but the graph is {}",
+                            direction_name(edge_directed),
+                            direction_name(directed)
+                        ),
+                    });
+                }
+                state.edges += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Reads a graph from an edge list, where each line holds the IDs of the source and target
+/// of an edge, separated by whitespace and optionally followed by the edge weight.
+/// Lines starting with `#` or `%` are comments, blank lines are skipped.
+///
+/// Nodes are added when their ID appears for the first time. Their weights are parsed from the ID,
+/// e.g. into a number or string. Edge weights are parsed from the rest of the line, so edges without
+/// weight need a weight type like `()` or `Option`.
+///
+/// The graph is built while reading, so apart from the graph itself, only a single line and a map
+/// from node IDs to nodes are kept in memory. `progress` is called every [PROGRESS_INTERVAL] lines
+/// and once at the end.
+pub fn read_edge_list<G, NW, EW, R, F>(reader: R, progress: F) -> Result<G, GraphIoError>
+where
+    G: GraphMut<NW, EW> + Default,
+    NW: DeserializeOwned,
+    EW: DeserializeOwned,
+    R: BufRead,
+    F: FnMut(&LoadProgress),
+{
+    let mut nodes: HashMap<String, _> = HashMap::new();
+    read_lines(reader, progress, |graph: &mut G, state, line| {
+        let line = line.trim_start();
+        if line.starts_with('#') || line.starts_with('%') {
+            return Ok(());
+        }
+        let (source, rest) = split_field(line);
+        let (target, weight) = split_field(rest);
+        if target.is_empty() {
+            return Err(GraphIoError::Syntax {
+                line: state.lines,
+                column: None,
+                message: "Expected the IDs of two nodes".to_string(),
+            });
+        }
+
+        let mut endpoint = |id: &str| -> Result<_, GraphIoError> {
+            match nodes.get(id) {
+                Some(n) => Ok(*n),
+                None => {
+                    let weight =
+                        weight_from_text(&[], Some(id)).map_err(|e| GraphIoError::Schema {
+                            line: Some(state.lines),
+                            message: format!("Couldn't deserialize node weight: {e}"),
+                        })?;
+                    let n = graph.add_node(weight);
+                    nodes.insert(id.to_string(), n);
+                    state.nodes += 1;
+                    Ok(n)
+                }
+            }
+        };
+        let (a, b) = (endpoint(source)?, endpoint(target)?);
+        let weight = weight_from_text(&[], Some(weight)).map_err(|e| GraphIoError::Schema {
+            line: Some(state.lines),
+            message: format!("Couldn't deserialize edge weight: {e}"),
+        })?;
+        graph.add_edge(a, b, weight);
+        state.edges += 1;
+        Ok(())
+    })
+}
+
+/// Splits the first whitespace-separated field off `text`, and returns it along with the rest.
+fn split_field(text: &str) -> (&str, &str) {
+    match text.split_once(char::is_whitespace) {
+        Some((field, rest)) => (field, rest.trim_start()),
+        None => (text, ""),
+    }
+}
+
+/// Builds a graph by calling `read_line` for each line of `reader` that isn't blank, with trailing whitespace trimmed.
+/// Leading whitespace is kept, so that positions within the line can be reported.
+///
+/// `read_line` adds the contents of the line to the graph and updates the node and edge counts.
+fn read_lines<G, NW, EW, R, F>(
+    mut reader: R,
+    mut progress: F,
+    mut read_line: impl FnMut(&mut G, &mut LoadProgress, &str) -> Result<(), GraphIoError>,
+) -> Result<G, GraphIoError>
+where
+    G: GraphMut<NW, EW> + Default,
+    R: BufRead,
+    F: FnMut(&LoadProgress),
+{
+    let mut graph = G::default();
+    let mut state = LoadProgress::default();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let len = reader.read_line(&mut line)?;
+        if len == 0 {
+            break;
+        }
+        state.bytes += len as u64;
+        state.lines += 1;
+        let text = line.trim_end();
+        if !text.is_empty() {
+            read_line(&mut graph, &mut state, text)?;
+        }
+        if state.lines % PROGRESS_INTERVAL == 0 {
+            progress(&state);
+        }
+    }
+    progress(&state);
+    Ok(graph)
+}
@@ -0,0 +1,160 @@
+use serde::{
+    de::{self, value::MapDeserializer, DeserializeOwned, IntoDeserializer, Unexpected, Visitor},
+    forward_to_deserialize_any, Deserializer,
+};
+use serde_json::Value;
+
+/// Deserializes a weight from named text fields, parsing their values as needed by the weight type.
+///
+/// Weights that can't be deserialized from the fields as a struct or map
+/// are deserialized from the single value `fallback`, if given.
+pub(super) fn weight_from_text<W: DeserializeOwned>(
+    fields: &[(&str, &str)],
+    fallback: Option<&str>,
+) -> Result<W, de::value::Error> {
+    let map = MapDeserializer::new(fields.iter().map(|(name, value)| (*name, TextValue(value))));
+    W::deserialize(map).or_else(|e| match fallback {
+        Some(value) => W::deserialize(TextValue(value)).map_err(|_| e),
+        None => Err(e),
+    })
+}
+
+/// Deserializes a value from its text, parsing it as needed by the weight type.
+///
+/// Empty text is read as None or unit, nested values such as structs, sequences and
+/// enum variants with data are parsed from JSON.
+struct TextValue<'a>(&'a str);
+
+impl TextValue<'_> {
+    fn json(&self) -> Result<Value, de::value::Error> {
+        serde_json::from_str(self.0).map_err(de::Error::custom)
+    }
+}
+
+/// Implements deserialization methods for values parsed with [str::parse].
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.trim().parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(de::Error::invalid_value(Unexpected::Str(self.0), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for TextValue<'de> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.json()?
+            .deserialize_seq(visitor)
+            .map_err(de::Error::custom)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.json()?
+            .deserialize_tuple(len, visitor)
+            .map_err(de::Error::custom)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.json()?
+            .deserialize_tuple_struct(name, len, visitor)
+            .map_err(de::Error::custom)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.json()?
+            .deserialize_map(visitor)
+            .map_err(de::Error::custom)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.json()?
+            .deserialize_struct(name, fields, visitor)
+            .map_err(de::Error::custom)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // Unit variants are written as their name, other variants as JSON objects.
+        if self.0.trim_start().starts_with('{') {
+            self.json()?
+                .deserialize_enum(name, variants, visitor)
+                .map_err(de::Error::custom)
+        } else {
+            visitor.visit_enum(self.0.into_deserializer())
+        }
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit_struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, de::value::Error> for TextValue<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
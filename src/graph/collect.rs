@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use petgraph::{
+    graph::{EdgeIndex, NodeIndex},
+    EdgeType,
+};
+
+use super::Graph;
+
+/// An owned copy of a graph, as created by [collect_graph] and [to_petgraph].
+///
+/// Unlike views such as [crate::filter_map::FilterMap], the copy doesn't borrow the graph it was created from,
+/// so it can outlive it, be sent to other threads or be serialized.
+#[derive(Clone, Debug)]
+pub struct CollectedGraph<NodeRef, EdgeRef, NodeWeight, EdgeWeight, Ty>
+where
+    Ty: EdgeType,
+{
+    /// The copied graph.
+    pub graph: petgraph::graph::Graph<NodeWeight, EdgeWeight, Ty>,
+    /// Maps each node of the original graph to its index in the copy.
+    pub node_indices: HashMap<NodeRef, NodeIndex>,
+    /// Maps each edge of the original graph to its index in the copy.
+    pub edge_indices: HashMap<EdgeRef, EdgeIndex>,
+}
+
+/// Copies `g` into an owned petgraph graph, converting weights with `map_node` and `map_edge`.
+///
+/// Nodes and edges are added in the order in which `g` yields them. Each edge leads
+/// from the first to the second node returned by [Graph::adjacent_nodes]. Whether the copy
+/// is directed is determined by `Ty`, not by `g`, so a directed graph may for example be
+/// collected into an undirected one.
+///
+/// To copy a view holding references as weights, e.g. a [crate::filter_map::FilterMap],
+/// dereference the weights before cloning them: `collect_graph(&view, |n| (*n).clone(), |e| (*e).clone())`.
+pub fn collect_graph<G, NW, EW, N, E, Ty, NodeFn, EdgeFn>(
+    g: &G,
+    mut map_node: NodeFn,
+    mut map_edge: EdgeFn,
+) -> CollectedGraph<G::NodeRef, G::EdgeRef, N, E, Ty>
+where
+    G: Graph<NW, EW>,
+    Ty: EdgeType,
+    NodeFn: FnMut(&NW) -> N,
+    EdgeFn: FnMut(&EW) -> E,
+{
+    let mut graph = petgraph::graph::Graph::with_capacity(g.count_nodes(), g.count_edges());
+    let node_indices: HashMap<_, _> = g
+        .nodes()
+        .map(|n| (n, graph.add_node(map_node(g.node_weight(n)))))
+        .collect();
+    let edge_indices = g
+        .edges()
+        .map(|e| {
+            let (a, b) = g.adjacent_nodes(e);
+            let weight = map_edge(g.edge_weight(e));
+            (
+                e,
+                graph.add_edge(node_indices[&a], node_indices[&b], weight),
+            )
+        })
+        .collect();
+    CollectedGraph {
+        graph,
+        node_indices,
+        edge_indices,
+    }
+}
+
+/// Copies `g` into an owned petgraph graph, cloning all weights.
+///
+/// See [collect_graph] for details.
+pub fn to_petgraph<G, NW, EW, Ty>(g: &G) -> CollectedGraph<G::NodeRef, G::EdgeRef, NW, EW, Ty>
+where
+    G: Graph<NW, EW>,
+    NW: Clone,
+    EW: Clone,
+    Ty: EdgeType,
+{
+    collect_graph(g, NW::clone, EW::clone)
+}
@@ -1,16 +1,95 @@
-use std::io;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+};
 
-use crate::graph::Graph;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    formats::{read_binary, write_binary},
+    graph::{Graph, GraphIoError, GraphMut},
+};
 
 /// Trait to serialize and deserialize a given Graph to a file.
 /// The file format depends on the graph type being used and can only
 /// be assumed compatible with the same graph type.
+/// To exchange graphs between different graph types, use the formats in [crate::formats],
+/// e.g. [crate::formats::write_json] and [crate::formats::read_json].
+///
+/// Implementations provide [GraphReadWriter::write_graph] and [GraphReadWriter::read_graph],
+/// which work on any stream, e.g. stdin, byte slices or in-memory buffers.
+/// The path-based methods are wrappers around them.
+///
+/// As an alternative to the format of the implementation, graphs can be stored in the compact binary
+/// format of [crate::formats::write_binary], which is much smaller and faster to read for large graphs.
 pub trait GraphReadWriter<NodeWeight, EdgeWeight>: Graph<NodeWeight, EdgeWeight> {
+    /// Serializes a given graph to `writer`.
+    /// The result tells us whether the operation succeeded or not.
+    fn write_graph<W: Write>(&self, writer: W) -> Result<(), GraphIoError>;
+
+    /// Deserializes a graph read from `reader`.
+    /// The result tells us whether the operation succeeded or not.
+    fn read_graph<R: Read>(reader: R) -> Result<Box<Self>, GraphIoError>;
+
     /// Serializes a given graph to a file defined by path.
+    /// This overwrites the file given under path.
     /// The result tells us whether the operation succeeded or not.
-    fn serialize_graph_to_file(&self, path: &str) -> Result<(), io::Error>;
+    fn serialize_graph_to_file(&self, path: &str) -> Result<(), GraphIoError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_graph(&mut writer)?;
+        Ok(writer.flush()?)
+    }
 
     /// Deserializes a graph stored in the given file.
     /// The result tells us whether the operation succeeded or not.
-    fn deserialize_graph_from_file(path: &str) -> Result<Box<Self>, io::Error>;
+    fn deserialize_graph_from_file(path: &str) -> Result<Box<Self>, GraphIoError> {
+        Self::read_graph(BufReader::new(File::open(path)?))
+    }
+
+    /// Serializes a given graph to `writer` in the binary format of [crate::formats::write_binary].
+    /// The result tells us whether the operation succeeded or not.
+    fn write_graph_binary<W: Write>(&self, writer: W) -> Result<(), GraphIoError>
+    where
+        Self: Sized,
+        NodeWeight: Serialize,
+        EdgeWeight: Serialize,
+    {
+        write_binary(self, writer)
+    }
+
+    /// Deserializes a graph in the binary format of [crate::formats::write_binary] read from `reader`.
+    /// The result tells us whether the operation succeeded or not.
+    fn read_graph_binary<R: Read>(reader: R) -> Result<Box<Self>, GraphIoError>
+    where
+        Self: GraphMut<NodeWeight, EdgeWeight> + Default,
+        NodeWeight: DeserializeOwned,
+        EdgeWeight: DeserializeOwned,
+    {
+        read_binary(reader).map(Box::new)
+    }
+
+    /// Serializes a given graph in the binary format to a file defined by path.
+    /// This overwrites the file given under path.
+    /// The result tells us whether the operation succeeded or not.
+    fn serialize_graph_to_binary_file(&self, path: &str) -> Result<(), GraphIoError>
+    where
+        Self: Sized,
+        NodeWeight: Serialize,
+        EdgeWeight: Serialize,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_graph_binary(&mut writer)?;
+        Ok(writer.flush()?)
+    }
+
+    /// Deserializes a graph stored in the binary format in the given file.
+    /// The result tells us whether the operation succeeded or not.
+    fn deserialize_graph_from_binary_file(path: &str) -> Result<Box<Self>, GraphIoError>
+    where
+        Self: GraphMut<NodeWeight, EdgeWeight> + Default,
+        NodeWeight: DeserializeOwned,
+        EdgeWeight: DeserializeOwned,
+    {
+        Self::read_graph_binary(BufReader::new(File::open(path)?))
+    }
 }
@@ -6,8 +6,9 @@ use super::Graph;
 pub fn incoming_nodes<'a, G, N, NW: 'a, EW: 'a>(g: &'a G, n: N) -> impl Iterator<Item = N> + 'a
 where
     G: Graph<NW, EW, NodeRef = N>,
+    N: Copy + 'a,
 {
-    g.incoming_edges(n).map(|e| g.adjacent_nodes(e).0)
+    g.incoming_edges(n).map(move |e| opposite_node(g, e, n))
 }
 
 /// Convenience Method to directly access the successor nodes of node `n` in Graph `g`.
@@ -16,6 +17,24 @@ where
 pub fn outgoing_nodes<'a, G, N, NW: 'a, EW: 'a>(g: &'a G, n: N) -> impl Iterator<Item = N> + 'a
 where
     G: Graph<NW, EW, NodeRef = N>,
+    N: Copy + 'a,
 {
-    g.outgoing_edges(n).map(|e| g.adjacent_nodes(e).1)
+    g.outgoing_edges(n).map(move |e| opposite_node(g, e, n))
+}
+
+/// Returns the node at the other end of `edge`, as seen from its adjacent node `n`.
+///
+/// Unlike looking at the source or destination returned by `adjacent_nodes`,
+/// this also works for undirected edges, which are incoming and outgoing edges
+/// of both their nodes. For self loops, `n` itself is returned.
+pub fn opposite_node<G, NW, EW>(g: &G, edge: G::EdgeRef, n: G::NodeRef) -> G::NodeRef
+where
+    G: Graph<NW, EW>,
+{
+    let (a, b) = g.adjacent_nodes(edge);
+    if a == n {
+        b
+    } else {
+        a
+    }
 }
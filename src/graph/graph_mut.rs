@@ -0,0 +1,60 @@
+use super::Graph;
+
+/// GraphMut extends the [Graph] trait by methods to modify a graph's structure and weights.
+///
+/// This allows writing code that builds or edits graphs independently of the storage backend.
+/// Whether references to other elements remain valid after removing nodes or edges
+/// depends on the backend, see the documentation of the respective implementation.
+pub trait GraphMut<NodeWeight, EdgeWeight>: Graph<NodeWeight, EdgeWeight> {
+    /// Adds a new node with the given weight to the graph, and returns its reference.
+    fn add_node(&mut self, weight: NodeWeight) -> Self::NodeRef;
+
+    /// Adds a new edge from node `from` to node `to` with the given weight, and returns its reference.
+    ///
+    /// For undirected graphs, the order of `from` and `to` doesn't matter.
+    fn add_edge(
+        &mut self,
+        from: Self::NodeRef,
+        to: Self::NodeRef,
+        weight: EdgeWeight,
+    ) -> Self::EdgeRef;
+
+    /// Checks if the graph can hold directed edges if `directed` is true, respectively undirected ones otherwise.
+    ///
+    /// The default implementation only accepts the direction given by [Graph::is_directed],
+    /// backends that allow mixing directed and undirected edges override it.
+    fn supports_edge_direction(&self, directed: bool) -> bool {
+        directed == self.is_directed()
+    }
+
+    /// Adds a new edge like [GraphMut::add_edge], which is directed if `directed` is true and undirected otherwise.
+    ///
+    /// Returns None without adding the edge if the graph can't hold an edge of that direction,
+    /// see [GraphMut::supports_edge_direction].
+    fn try_add_edge_with_direction(
+        &mut self,
+        from: Self::NodeRef,
+        to: Self::NodeRef,
+        weight: EdgeWeight,
+        directed: bool,
+    ) -> Option<Self::EdgeRef> {
+        self.supports_edge_direction(directed)
+            .then(|| self.add_edge(from, to, weight))
+    }
+
+    /// Removes a node along with all its adjacent edges from the graph.
+    ///
+    /// Returns the weight of the removed node, or None if the node doesn't exist.
+    fn remove_node(&mut self, node: Self::NodeRef) -> Option<NodeWeight>;
+
+    /// Removes an edge from the graph.
+    ///
+    /// Returns the weight of the removed edge, or None if the edge doesn't exist.
+    fn remove_edge(&mut self, edge: Self::EdgeRef) -> Option<EdgeWeight>;
+
+    /// Retrieve a mutable weight from a node reference.
+    fn node_weight_mut(&mut self, node: Self::NodeRef) -> &mut NodeWeight;
+
+    /// Retrieve a mutable weight from an edge reference.
+    fn edge_weight_mut(&mut self, edge: Self::EdgeRef) -> &mut EdgeWeight;
+}
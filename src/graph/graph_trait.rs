@@ -41,6 +41,30 @@ pub trait Graph<NodeWeight, EdgeWeight> {
     /// For undirected graphs this is equivalent to calling `adjacent_edges`.
     fn outgoing_edges(&self, node: Self::NodeRef) -> Self::OutgoingEdgesIterator<'_>;
 
+    /// Returns the number of incoming edges of a node.
+    ///
+    /// The default implementation counts the `incoming_edges` of the node,
+    /// storage backends may override it with a constant time lookup.
+    fn in_degree(&self, node: Self::NodeRef) -> usize {
+        self.incoming_edges(node).count()
+    }
+
+    /// Returns the number of outgoing edges of a node.
+    ///
+    /// The default implementation counts the `outgoing_edges` of the node,
+    /// storage backends may override it with a constant time lookup.
+    fn out_degree(&self, node: Self::NodeRef) -> usize {
+        self.outgoing_edges(node).count()
+    }
+
+    /// Returns the number of adjacent edges of a node.
+    ///
+    /// The default implementation counts the `adjacent_edges` of the node,
+    /// storage backends may override it with a constant time lookup.
+    fn degree(&self, node: Self::NodeRef) -> usize {
+        self.adjacent_edges(node).count()
+    }
+
     /// Gets a readonly handle of the nodes an edge connects.
     ///
     /// If the edge is directed, the first node is its source, and the second node its destination.
@@ -52,6 +76,37 @@ pub trait Graph<NodeWeight, EdgeWeight> {
     /// Retrieve weight from an edge reference.
     fn edge_weight(&self, edge: Self::EdgeRef) -> &EdgeWeight;
 
+    /// Checks if the given node reference is valid for this graph.
+    ///
+    /// The default implementation iterates over all nodes,
+    /// storage backends should provide a faster lookup if possible.
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        self.nodes().any(|n| n == node)
+    }
+
+    /// Checks if the given edge reference is valid for this graph.
+    ///
+    /// The default implementation iterates over all edges,
+    /// storage backends should provide a faster lookup if possible.
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        self.edges().any(|e| e == edge)
+    }
+
+    /// Like [Graph::adjacent_nodes], but returns None instead of panicking if the edge reference is invalid.
+    fn try_adjacent_nodes(&self, edge: Self::EdgeRef) -> Option<(Self::NodeRef, Self::NodeRef)> {
+        self.contains_edge(edge).then(|| self.adjacent_nodes(edge))
+    }
+
+    /// Like [Graph::node_weight], but returns None instead of panicking if the node reference is invalid.
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        self.contains_node(node).then(|| self.node_weight(node))
+    }
+
+    /// Like [Graph::edge_weight], but returns None instead of panicking if the edge reference is invalid.
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        self.contains_edge(edge).then(|| self.edge_weight(edge))
+    }
+
     type NodeWeightsIterator<'a>: Iterator<Item = &'a NodeWeight>
     where
         Self: 'a,
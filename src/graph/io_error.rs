@@ -0,0 +1,142 @@
+use std::{error::Error, fmt, io};
+
+/// Error returned when reading or writing a graph fails.
+///
+/// Tells I/O failures, like missing files, apart from problems with the contents of a file,
+/// and reports the position of the problem where it is known.
+/// Lines and columns start at 1, columns are counted in characters.
+#[derive(Debug)]
+pub enum GraphIoError {
+    /// Reading from or writing to the underlying file or stream failed.
+    Io(io::Error),
+    /// The input is malformed, e.g. invalid JSON or XML, or ends unexpectedly.
+    Syntax {
+        line: u64,
+        /// Column of the problem, if known.
+        column: Option<u64>,
+        message: String,
+    },
+    /// The contents don't fit the graph or weight types, e.g. because a weight can't be deserialized
+    /// or the direction of the graph doesn't match. Also returned by writers for graphs that can't be
+    /// represented in a format.
+    Schema {
+        /// Line of the problem, if known.
+        line: Option<u64>,
+        message: String,
+    },
+    /// An edge refers to a node that doesn't exist.
+    DanglingEdge {
+        /// Line of the edge, if known.
+        line: Option<u64>,
+        /// ID of the missing node, as used in the input.
+        node: String,
+    },
+}
+
+impl GraphIoError {
+    /// Creates a [GraphIoError::Schema] error without position.
+    pub(crate) fn schema(message: impl Into<String>) -> Self {
+        GraphIoError::Schema {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the line of the problem, if known.
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            GraphIoError::Io(_) => None,
+            GraphIoError::Syntax { line, .. } => Some(*line),
+            GraphIoError::Schema { line, .. } | GraphIoError::DanglingEdge { line, .. } => *line,
+        }
+    }
+}
+
+impl fmt::Display for GraphIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let GraphIoError::Schema {
+            line: Some(line), ..
+        }
+        | GraphIoError::DanglingEdge {
+            line: Some(line), ..
+        } = self
+        {
+            write!(f, "Line {line}: ")?;
+        }
+        match self {
+            GraphIoError::Io(e) => write!(f, "{e}"),
+            GraphIoError::Syntax {
+                line,
+                column: Some(column),
+                message,
+            } => write!(f, "Syntax error in line {line}, column {column}: {message}"),
+            GraphIoError::Syntax {
+                line,
+                column: None,
+                message,
+            } => write!(f, "Syntax error in line {line}: {message}"),
+            GraphIoError::Schema { message, .. } => write!(f, "{message}"),
+            GraphIoError::DanglingEdge { node, .. } => {
+                write!(f, "Edge refers to node {node:?}, which doesn't exist")
+            }
+        }
+    }
+}
+
+impl Error for GraphIoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GraphIoError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GraphIoError {
+    fn from(e: io::Error) -> Self {
+        GraphIoError::Io(e)
+    }
+}
+
+/// Converts errors of serde_json, keeping the position of syntax errors and invalid data.
+impl From<serde_json::Error> for GraphIoError {
+    fn from(e: serde_json::Error) -> Self {
+        use serde_json::error::Category;
+        let (line, column) = (e.line() as u64, e.column() as u64);
+        match e.classify() {
+            Category::Io => GraphIoError::Io(e.into()),
+            Category::Syntax | Category::Eof => GraphIoError::Syntax {
+                line,
+                column: Some(column),
+                message: strip_position(&e),
+            },
+            // Errors of serializers have no position.
+            Category::Data if line == 0 => GraphIoError::schema(e.to_string()),
+            Category::Data => GraphIoError::Schema {
+                line: Some(line),
+                message: strip_position(&e),
+            },
+        }
+    }
+}
+
+/// Returns the message of a serde_json error, without the position it appends.
+fn strip_position(e: &serde_json::Error) -> String {
+    let message = e.to_string();
+    let suffix = format!(" at line {} column {}", e.line(), e.column());
+    message
+        .strip_suffix(&suffix)
+        .unwrap_or(&message)
+        .to_string()
+}
+
+/// Allows to use [GraphIoError] where [io::Error] is expected.
+/// I/O errors are passed on as they are, other errors are returned as [io::ErrorKind::InvalidData].
+impl From<GraphIoError> for io::Error {
+    fn from(e: GraphIoError) -> Self {
+        match e {
+            GraphIoError::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
@@ -2,11 +2,14 @@
 //!
 //! This approach theoretically allows swapping out storage backends for graphs, and allows more implementation flexibility for the return type of graph operations.
 //!
-//! However, petgraphs Graph type is currently the only storage backend supported by the implementations in this crate itself.
+//! The implementations in this crate itself support petgraphs Graph, StableGraph and GraphMap types as storage backends, as well as the read-optimized [crate::csr_graph::CsrGraph] and [crate::mixed_graph::MixedGraph], which allows mixing directed and undirected edges.
 
 /// Serializing graphs to files.
 mod file_io;
 pub use file_io::GraphReadWriter;
+/// Errors of reading and writing graphs.
+mod io_error;
+pub use io_error::GraphIoError;
 /// Printing graph visualizations in graphviz dot format.
 mod print;
 pub use print::VizDotGraph;
@@ -18,3 +21,15 @@ pub use self::graph_helpers::*;
 /// Generic graph trait specification
 mod graph_trait;
 pub use graph_trait::Graph;
+
+/// Trait extension for modifying graphs.
+mod graph_mut;
+pub use graph_mut::GraphMut;
+
+/// Secondary indexes over node weights.
+mod property_index;
+pub use property_index::PropertyIndex;
+
+/// Copying graphs into owned petgraph graphs.
+mod collect;
+pub use collect::{collect_graph, to_petgraph, CollectedGraph};
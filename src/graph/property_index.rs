@@ -0,0 +1,48 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::Graph;
+
+/// A secondary hash index over the nodes of a graph, keyed on a projection of their weights.
+///
+/// The index maps each key to all nodes whose weight projects to that key,
+/// e.g. all nodes of a given kind or name. It allows finding those nodes
+/// without scanning the whole graph.
+///
+/// The index is a snapshot of the graph it was built from,
+/// it is not updated when nodes of that graph are added, removed or modified.
+pub struct PropertyIndex<Key, NodeRef> {
+    nodes: HashMap<Key, Vec<NodeRef>>,
+}
+
+impl<Key, NodeRef> PropertyIndex<Key, NodeRef>
+where
+    Key: Eq + Hash,
+    NodeRef: Copy,
+{
+    /// Builds an index over all nodes of `graph`, keyed on `projection` of their weights.
+    ///
+    /// Nodes for which `projection` returns None are left out of the index.
+    pub fn new<G, NodeWeight, EdgeWeight, P>(graph: &G, projection: P) -> Self
+    where
+        G: Graph<NodeWeight, EdgeWeight, NodeRef = NodeRef>,
+        P: Fn(&NodeWeight) -> Option<Key>,
+    {
+        let mut nodes: HashMap<Key, Vec<NodeRef>> = HashMap::new();
+        for n in graph.nodes() {
+            if let Some(key) = projection(graph.node_weight(n)) {
+                nodes.entry(key).or_default().push(n);
+            }
+        }
+        Self { nodes }
+    }
+
+    /// Returns all nodes indexed under `key`, in the order in which the graph yielded them.
+    pub fn get(&self, key: &Key) -> &[NodeRef] {
+        self.nodes.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the number of distinct keys in this index.
+    pub fn count_keys(&self) -> usize {
+        self.nodes.len()
+    }
+}
@@ -22,5 +22,21 @@ pub mod filter_map;
 /// Pattern matching on graphs.
 pub mod pattern_matching;
 
-/// Implements the traits defined in this crate for [``::petgraph::graph::Graph``].
+/// Views of graphs with reversed or ignored edge directions.
+pub mod adapters;
+
+/// Graph algorithms that are generic over storage backends.
+pub mod algorithms;
+
+/// Read-optimized graph storage backend in compressed sparse row layout.
+pub mod csr_graph;
+
+/// Graph storage backend supporting both directed and undirected edges.
+pub mod mixed_graph;
+
+/// Reading and writing graphs in file formats of other tools.
+pub mod formats;
+
+/// Implements the traits defined in this crate for [``::petgraph::graph::Graph``],
+/// [``::petgraph::stable_graph::StableGraph``] and [``::petgraph::graphmap::GraphMap``].
 mod petgraph;
@@ -0,0 +1,423 @@
+//!
+//! This module provides [mixed_graph::MixedGraph], a graph storage backend
+//! that allows directed and undirected edges within the same graph.
+//!
+//! Directed edges are only reported as outgoing edges of their source, and as incoming edges of their
+//! target node. Undirected edges are reported as both incoming and outgoing edges of both their nodes.
+//! [graph::Graph::is_directed_edge] tells those two kinds of edges apart.
+//!
+//! Pattern matching respects the direction of edges in mixed graphs: A directed pattern edge
+//! matches directed edges with the same orientation, as well as undirected edges.
+
+use crate::graph::{self, GraphMut};
+
+/// Node reference type of [MixedGraph].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MixedNodeIndex(usize);
+
+impl MixedNodeIndex {
+    /// Creates a node reference from its position.
+    pub fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// Returns the position of this node.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Edge reference type of [MixedGraph].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MixedEdgeIndex(usize);
+
+impl MixedEdgeIndex {
+    /// Creates an edge reference from its position.
+    pub fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// Returns the position of this edge.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Node entry, holding the weight and all adjacent edges of a node.
+struct MixedNode<NodeWeight> {
+    weight: NodeWeight,
+    /// All edges starting or ending at this node. Self loops are listed once.
+    edges: Vec<MixedEdgeIndex>,
+}
+
+/// Edge entry.
+struct MixedEdge<EdgeWeight> {
+    weight: EdgeWeight,
+    source: MixedNodeIndex,
+    target: MixedNodeIndex,
+    directed: bool,
+}
+
+/// In-memory graph supporting both directed and undirected edges.
+///
+/// Removing nodes or edges leaves a vacant slot behind, so references
+/// to the remaining elements stay valid.
+///
+/// Since the orientation of at least some edges matters, a mixed graph
+/// always reports itself as directed through [graph::Graph::is_directed].
+pub struct MixedGraph<NodeWeight, EdgeWeight> {
+    nodes: Vec<Option<MixedNode<NodeWeight>>>,
+    edges: Vec<Option<MixedEdge<EdgeWeight>>>,
+    node_count: usize,
+    edge_count: usize,
+}
+
+impl<NodeWeight, EdgeWeight> Default for MixedGraph<NodeWeight, EdgeWeight> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<NodeWeight, EdgeWeight> MixedGraph<NodeWeight, EdgeWeight> {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            edges: vec![],
+            node_count: 0,
+            edge_count: 0,
+        }
+    }
+
+    /// Adds a new node to the graph, and returns its reference.
+    pub fn add_node(&mut self, weight: NodeWeight) -> MixedNodeIndex {
+        self.nodes.push(Some(MixedNode {
+            weight,
+            edges: vec![],
+        }));
+        self.node_count += 1;
+        MixedNodeIndex(self.nodes.len() - 1)
+    }
+
+    /// Adds a new directed edge from `source` to `target`, and returns its reference.
+    ///
+    /// ## Panics:
+    /// Panics if one of the nodes doesn't exist.
+    pub fn add_directed_edge(
+        &mut self,
+        source: MixedNodeIndex,
+        target: MixedNodeIndex,
+        weight: EdgeWeight,
+    ) -> MixedEdgeIndex {
+        self.insert_edge(source, target, weight, true)
+    }
+
+    /// Adds a new undirected edge between `a` and `b`, and returns its reference.
+    ///
+    /// ## Panics:
+    /// Panics if one of the nodes doesn't exist.
+    pub fn add_undirected_edge(
+        &mut self,
+        a: MixedNodeIndex,
+        b: MixedNodeIndex,
+        weight: EdgeWeight,
+    ) -> MixedEdgeIndex {
+        self.insert_edge(a, b, weight, false)
+    }
+
+    /// Inserts an edge and registers it at its nodes.
+    fn insert_edge(
+        &mut self,
+        source: MixedNodeIndex,
+        target: MixedNodeIndex,
+        weight: EdgeWeight,
+        directed: bool,
+    ) -> MixedEdgeIndex {
+        assert!(
+            self.node(source).is_some() && self.node(target).is_some(),
+            "Couldn't add edge: Node reference invalid."
+        );
+        let edge = MixedEdgeIndex(self.edges.len());
+        self.edges.push(Some(MixedEdge {
+            weight,
+            source,
+            target,
+            directed,
+        }));
+        self.edge_count += 1;
+
+        self.nodes[source.0].as_mut().unwrap().edges.push(edge);
+        if source != target {
+            self.nodes[target.0].as_mut().unwrap().edges.push(edge);
+        }
+        edge
+    }
+
+    /// Removes a node and all its adjacent edges. Returns the weight of the removed node,
+    /// or None if it doesn't exist.
+    pub fn remove_node(&mut self, node: MixedNodeIndex) -> Option<NodeWeight> {
+        let edges = self.node(node)?.edges.clone();
+        for edge in edges {
+            self.remove_edge(edge);
+        }
+        self.node_count -= 1;
+        self.nodes[node.0].take().map(|n| n.weight)
+    }
+
+    /// Removes an edge. Returns its weight, or None if it doesn't exist.
+    pub fn remove_edge(&mut self, edge: MixedEdgeIndex) -> Option<EdgeWeight> {
+        let removed = self.edges.get_mut(edge.0)?.take()?;
+        self.edge_count -= 1;
+        for n in [removed.source, removed.target] {
+            if let Some(node) = self.nodes[n.0].as_mut() {
+                node.edges.retain(|e| *e != edge);
+            }
+        }
+        Some(removed.weight)
+    }
+
+    /// Looks up a node entry.
+    fn node(&self, node: MixedNodeIndex) -> Option<&MixedNode<NodeWeight>> {
+        self.nodes.get(node.0).and_then(Option::as_ref)
+    }
+
+    /// Looks up an edge entry.
+    fn edge(&self, edge: MixedEdgeIndex) -> Option<&MixedEdge<EdgeWeight>> {
+        self.edges.get(edge.0).and_then(Option::as_ref)
+    }
+
+    /// Looks up the adjacent edges of a node, panicking on invalid references.
+    fn node_edges(&self, node: MixedNodeIndex) -> &[MixedEdgeIndex] {
+        &self
+            .node(node)
+            .expect("Couldn't find adjacent edges: Node reference invalid.")
+            .edges
+    }
+
+    /// Checks whether `edge` may be traversed from `from` to the opposite node.
+    fn leaves(&self, edge: MixedEdgeIndex, from: MixedNodeIndex) -> bool {
+        let edge = self.edge(edge).unwrap();
+        !edge.directed || edge.source == from
+    }
+
+    /// Checks whether `edge` may be traversed from the opposite node to `to`.
+    fn enters(&self, edge: MixedEdgeIndex, to: MixedNodeIndex) -> bool {
+        let edge = self.edge(edge).unwrap();
+        !edge.directed || edge.target == to
+    }
+}
+
+impl<NodeWeight, EdgeWeight> graph::Graph<NodeWeight, EdgeWeight>
+    for MixedGraph<NodeWeight, EdgeWeight>
+{
+    type NodeRef = MixedNodeIndex;
+
+    type EdgeRef = MixedEdgeIndex;
+
+    fn is_directed(&self) -> bool {
+        true
+    }
+
+    fn is_directed_edge(&self, edge: Self::EdgeRef) -> bool {
+        self.edge(edge)
+            .expect("Couldn't find edge: Edge reference invalid.")
+            .directed
+    }
+
+    type AdjacentEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn adjacent_edges(&self, node: Self::NodeRef) -> Self::AdjacentEdgesIterator<'_> {
+        self.node_edges(node).iter().copied()
+    }
+
+    type IncomingEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn incoming_edges(&self, node: Self::NodeRef) -> Self::IncomingEdgesIterator<'_> {
+        self.node_edges(node)
+            .iter()
+            .copied()
+            .filter(move |e| self.enters(*e, node))
+    }
+
+    type OutgoingEdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn outgoing_edges(&self, node: Self::NodeRef) -> Self::OutgoingEdgesIterator<'_> {
+        self.node_edges(node)
+            .iter()
+            .copied()
+            .filter(move |e| self.leaves(*e, node))
+    }
+
+    fn degree(&self, node: Self::NodeRef) -> usize {
+        self.node_edges(node).len()
+    }
+
+    fn adjacent_nodes(&self, edge: Self::EdgeRef) -> (Self::NodeRef, Self::NodeRef) {
+        let edge = self
+            .edge(edge)
+            .expect("Couldn't find edge endpoint references: Edge reference invalid.");
+        (edge.source, edge.target)
+    }
+
+    fn node_weight(&self, node: Self::NodeRef) -> &NodeWeight {
+        &self
+            .node(node)
+            .expect("Couldn't find node weight: Node reference invalid.")
+            .weight
+    }
+
+    fn edge_weight(&self, edge: Self::EdgeRef) -> &EdgeWeight {
+        &self
+            .edge(edge)
+            .expect("Couldn't find edge weight: Edge reference invalid.")
+            .weight
+    }
+
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        self.node(node).is_some()
+    }
+
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        self.edge(edge).is_some()
+    }
+
+    fn try_adjacent_nodes(&self, edge: Self::EdgeRef) -> Option<(Self::NodeRef, Self::NodeRef)> {
+        self.edge(edge).map(|e| (e.source, e.target))
+    }
+
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        self.node(node).map(|n| &n.weight)
+    }
+
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        self.edge(edge).map(|e| &e.weight)
+    }
+
+    type NodeWeightsIterator<'a>
+        = impl Iterator<Item = &'a NodeWeight>
+    where
+        Self: 'a,
+        NodeWeight: 'a;
+
+    fn node_weights(&self) -> Self::NodeWeightsIterator<'_> {
+        self.nodes.iter().flatten().map(|n| &n.weight)
+    }
+
+    type EdgeWeightsIterator<'a>
+        = impl Iterator<Item = &'a EdgeWeight>
+    where
+        Self: 'a,
+        EdgeWeight: 'a;
+
+    fn edge_weights(&self) -> Self::EdgeWeightsIterator<'_> {
+        self.edges.iter().flatten().map(|e| &e.weight)
+    }
+
+    type NodesIterator<'a>
+        = impl Iterator<Item = Self::NodeRef> + 'a
+    where
+        Self: 'a;
+
+    fn nodes(&self) -> Self::NodesIterator<'_> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.is_some())
+            .map(|(i, _)| MixedNodeIndex(i))
+    }
+
+    type EdgesIterator<'a>
+        = impl Iterator<Item = Self::EdgeRef> + 'a
+    where
+        Self: 'a;
+
+    fn edges(&self) -> Self::EdgesIterator<'_> {
+        self.edges
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.is_some())
+            .map(|(i, _)| MixedEdgeIndex(i))
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.node_count
+    }
+
+    fn count_edges(&self) -> usize {
+        self.edge_count
+    }
+}
+
+/// GraphMut implementation for mixed graphs.
+///
+/// Edges added through [GraphMut::add_edge] are directed,
+/// use [MixedGraph::add_undirected_edge] or [GraphMut::try_add_edge_with_direction] to add undirected ones.
+impl<NodeWeight, EdgeWeight> GraphMut<NodeWeight, EdgeWeight>
+    for MixedGraph<NodeWeight, EdgeWeight>
+{
+    fn add_node(&mut self, weight: NodeWeight) -> MixedNodeIndex {
+        MixedGraph::add_node(self, weight)
+    }
+
+    fn add_edge(
+        &mut self,
+        from: MixedNodeIndex,
+        to: MixedNodeIndex,
+        weight: EdgeWeight,
+    ) -> MixedEdgeIndex {
+        self.add_directed_edge(from, to, weight)
+    }
+
+    fn supports_edge_direction(&self, _directed: bool) -> bool {
+        true
+    }
+
+    fn try_add_edge_with_direction(
+        &mut self,
+        from: MixedNodeIndex,
+        to: MixedNodeIndex,
+        weight: EdgeWeight,
+        directed: bool,
+    ) -> Option<MixedEdgeIndex> {
+        Some(if directed {
+            self.add_directed_edge(from, to, weight)
+        } else {
+            self.add_undirected_edge(from, to, weight)
+        })
+    }
+
+    fn remove_node(&mut self, node: MixedNodeIndex) -> Option<NodeWeight> {
+        MixedGraph::remove_node(self, node)
+    }
+
+    fn remove_edge(&mut self, edge: MixedEdgeIndex) -> Option<EdgeWeight> {
+        MixedGraph::remove_edge(self, edge)
+    }
+
+    fn node_weight_mut(&mut self, node: MixedNodeIndex) -> &mut NodeWeight {
+        &mut self
+            .nodes
+            .get_mut(node.0)
+            .and_then(Option::as_mut)
+            .expect("Couldn't find node weight: Node reference invalid.")
+            .weight
+    }
+
+    fn edge_weight_mut(&mut self, edge: MixedEdgeIndex) -> &mut EdgeWeight {
+        &mut self
+            .edges
+            .get_mut(edge.0)
+            .and_then(Option::as_mut)
+            .expect("Couldn't find edge weight: Edge reference invalid.")
+            .weight
+    }
+}
@@ -1,4 +1,4 @@
-use std::hash::Hash;
+use std::{collections::HashMap, hash::Hash};
 
 use crate::{filter_map::FilterMap, graph::Graph};
 
@@ -53,3 +53,26 @@ pub trait SubgraphAlgorithm<
 /// Type definition of MatchedGraph.
 pub type MatchedGraph<'a, N, E, P> =
     FilterMap<'a, PatternElement<N>, PatternElement<E>, &'a N, &'a E, P>;
+
+/// The base graph elements that the elements of a [MatchedGraph] have been matched to.
+///
+/// Maps the pattern node and edge references `NRef`/`ERef` to the base graph references `N2Ref`/`E2Ref`.
+/// Like in the MatchedGraph, hidden pattern elements don't appear.
+#[derive(Clone, Debug)]
+pub struct MatchedRefs<NRef, ERef, N2Ref, E2Ref> {
+    /// Matched base graph nodes, by pattern node.
+    pub nodes: HashMap<NRef, N2Ref>,
+    /// Matched base graph edges, by pattern edge.
+    pub edges: HashMap<ERef, E2Ref>,
+}
+
+/// A [MatchedGraph] of pattern `P` together with the [MatchedRefs] of its elements in base graph `B`.
+pub type MatchWithRefs<'a, N, E, P, B> = (
+    MatchedGraph<'a, N, E, P>,
+    MatchedRefs<
+        <P as Graph<PatternElement<N>, PatternElement<E>>>::NodeRef,
+        <P as Graph<PatternElement<N>, PatternElement<E>>>::EdgeRef,
+        <B as Graph<N, E>>::NodeRef,
+        <B as Graph<N, E>>::EdgeRef,
+    >,
+);
@@ -0,0 +1,230 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{
+    filter_map::FilterMap,
+    graph::Graph,
+    pattern_matching::{new_pattern, vf_algorithms::VfState, PatternGraph},
+};
+
+/// A bijection between the elements of two isomorphic graphs, as found by [is_isomorphic].
+#[derive(Clone, Debug)]
+pub struct Isomorphism<NodeRef1, EdgeRef1, NodeRef2, EdgeRef2> {
+    /// Maps each node of the first graph to a node of the second graph.
+    pub nodes: HashMap<NodeRef1, NodeRef2>,
+    /// Maps each edge of the first graph to an edge of the second graph.
+    pub edges: HashMap<EdgeRef1, EdgeRef2>,
+}
+
+/// The [Isomorphism] between graphs `G1` and `G2`.
+type GraphIsomorphism<G1, G2, N1, E1, N2, E2> = Isomorphism<
+    <G1 as Graph<N1, E1>>::NodeRef,
+    <G1 as Graph<N1, E1>>::EdgeRef,
+    <G2 as Graph<N2, E2>>::NodeRef,
+    <G2 as Graph<N2, E2>>::EdgeRef,
+>;
+
+/// Edges of a graph grouped by their adjacent nodes, so that parallel edges end up in the same group.
+///
+/// Directed and undirected edges are kept in separate groups.
+struct EdgeGroups<NodeRef, EdgeRef> {
+    /// Source and destination of the edges of each group, and whether they are directed.
+    /// For undirected edges, the smaller node comes first.
+    keys: Vec<(NodeRef, NodeRef, bool)>,
+    edges: Vec<Vec<EdgeRef>>,
+    /// Position of each key in `keys`.
+    positions: HashMap<(NodeRef, NodeRef, bool), usize>,
+}
+
+impl<NodeRef, EdgeRef> EdgeGroups<NodeRef, EdgeRef>
+where
+    NodeRef: Copy + Ord + Hash,
+    EdgeRef: Copy,
+{
+    fn new<G, NW, EW>(g: &G) -> Self
+    where
+        G: Graph<NW, EW, NodeRef = NodeRef, EdgeRef = EdgeRef>,
+    {
+        let mut groups = Self {
+            keys: vec![],
+            edges: vec![],
+            positions: HashMap::new(),
+        };
+        for e in g.edges() {
+            let (a, b) = g.adjacent_nodes(e);
+            let directed = g.is_directed_edge(e);
+            let group = groups.position(a, b, directed).unwrap_or_else(|| {
+                let key = Self::key(a, b, directed);
+                groups.positions.insert(key, groups.keys.len());
+                groups.keys.push(key);
+                groups.edges.push(vec![]);
+                groups.keys.len() - 1
+            });
+            groups.edges[group].push(e);
+        }
+        groups
+    }
+
+    /// Returns the key of the group of an edge between `a` and `b`.
+    fn key(a: NodeRef, b: NodeRef, directed: bool) -> (NodeRef, NodeRef, bool) {
+        if directed {
+            (a, b, true)
+        } else {
+            (a.min(b), a.max(b), false)
+        }
+    }
+
+    /// Returns the position of the group of edges between `a` and `b`, if there are any.
+    fn position(&self, a: NodeRef, b: NodeRef, directed: bool) -> Option<usize> {
+        self.positions.get(&Self::key(a, b, directed)).copied()
+    }
+}
+
+/// Finds a perfect matching in the bipartite graph with `len` nodes on each side,
+/// where `compatible(i, j)` tells whether left node i and right node j may be paired.
+///
+/// Returns the right node paired with each left node, or None if there is no perfect matching.
+fn perfect_matching(len: usize, compatible: impl Fn(usize, usize) -> bool) -> Option<Vec<usize>> {
+    /// Tries to pair left node i, moving earlier pairs along an augmenting path if needed.
+    fn augment(
+        i: usize,
+        compatible: &impl Fn(usize, usize) -> bool,
+        visited: &mut [bool],
+        left_of: &mut [Option<usize>],
+    ) -> bool {
+        for j in 0..left_of.len() {
+            if visited[j] || !compatible(i, j) {
+                continue;
+            }
+            visited[j] = true;
+            if left_of[j].is_none_or(|k| augment(k, compatible, visited, left_of)) {
+                left_of[j] = Some(i);
+                return true;
+            }
+        }
+        false
+    }
+
+    let mut left_of = vec![None; len];
+    for i in 0..len {
+        if !augment(i, &compatible, &mut vec![false; len], &mut left_of) {
+            return None;
+        }
+    }
+    let mut right_of = vec![0; len];
+    for (j, i) in left_of.into_iter().enumerate() {
+        right_of[i.unwrap()] = j;
+    }
+    Some(right_of)
+}
+
+/// Checks whether the graphs `g1` and `g2` are isomorphic, i.e. whether there is a bijection between
+/// their nodes and a bijection between their edges, so that matching edges connect matching nodes.
+/// Directed edges must be matched to directed edges with the same orientation,
+/// undirected edges to undirected edges.
+///
+/// Additionally, `node_eq` and `edge_eq` must hold for the weights of all matched elements.
+/// The graphs may use different storage backends and weight types.
+///
+/// Returns such a pair of bijections if one exists, or None otherwise.
+/// The search is based on the VF algorithms in [crate::pattern_matching::vf_algorithms].
+pub fn is_isomorphic<G1, G2, N1, E1, N2, E2, NodeEq, EdgeEq>(
+    g1: &G1,
+    g2: &G2,
+    node_eq: NodeEq,
+    edge_eq: EdgeEq,
+) -> Option<GraphIsomorphism<G1, G2, N1, E1, N2, E2>>
+where
+    G1: Graph<N1, E1>,
+    G2: Graph<N2, E2>,
+    NodeEq: Fn(&N1, &N2) -> bool,
+    EdgeEq: Fn(&E1, &E2) -> bool,
+{
+    if g1.count_nodes() != g2.count_nodes() || g1.count_edges() != g2.count_edges() {
+        return None;
+    }
+    let groups1 = EdgeGroups::new(g1);
+    let groups2 = EdgeGroups::new(g2);
+    if groups1.edges.len() != groups2.edges.len() {
+        return None;
+    }
+
+    // Translate g1 into a pattern that only matches g2 nodes and edge groups that fit.
+    // The weights of the matched graph are positions in `nodes2` and `groups2`.
+    let nodes1: Vec<_> = g1.nodes().collect();
+    let nodes2: Vec<_> = g2.nodes().collect();
+    let positions2: HashMap<_, _> = nodes2.iter().enumerate().map(|(j, m)| (*m, j)).collect();
+    let mut pattern = new_pattern::<usize, usize>();
+    let mut pattern_nodes = HashMap::new();
+    for n in &nodes1 {
+        let candidates: HashSet<_> = nodes2
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| {
+                g1.in_degree(*n) == g2.in_degree(**m)
+                    && g1.out_degree(*n) == g2.out_degree(**m)
+                    && node_eq(g1.node_weight(*n), g2.node_weight(**m))
+            })
+            .map(|(j, _)| j)
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        pattern_nodes.insert(*n, pattern.add_node(move |j| candidates.contains(j)));
+    }
+    for ((a, b, directed), edges1) in groups1.keys.iter().zip(&groups1.edges) {
+        let candidates: HashSet<_> = groups2
+            .keys
+            .iter()
+            .zip(&groups2.edges)
+            .enumerate()
+            .filter(|(_, ((_, _, directed2), edges2))| {
+                directed == directed2
+                    && edges1.len() == edges2.len()
+                    && perfect_matching(edges1.len(), |i, k| {
+                        edge_eq(g1.edge_weight(edges1[i]), g2.edge_weight(edges2[k]))
+                    })
+                    .is_some()
+            })
+            .map(|(j, _)| j)
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        pattern.add_edge(pattern_nodes[a], pattern_nodes[b], move |j| {
+            candidates.contains(j)
+        });
+    }
+    let base = FilterMap::general_filter_map(
+        g2,
+        |_, m| Some(positions2[&m]),
+        |g2, e| {
+            let (a, b) = g2.adjacent_nodes(e);
+            groups2.position(a, b, g2.is_directed_edge(e))
+        },
+    );
+
+    let nodes = if nodes1.is_empty() {
+        HashMap::new()
+    } else {
+        let result = VfState::eval_first(&pattern, &base)?;
+        nodes1
+            .iter()
+            .map(|n| (*n, nodes2[**result.node_weight(pattern_nodes[n])]))
+            .collect()
+    };
+    let mut edges = HashMap::new();
+    for (edges1, (a, b, directed)) in groups1.edges.iter().zip(&groups1.keys) {
+        // Every group of g1 has been matched to a compatible group of g2 connecting the matched nodes.
+        let group2 = groups2.position(nodes[a], nodes[b], *directed).unwrap();
+        let edges2 = &groups2.edges[group2];
+        let pairs = perfect_matching(edges1.len(), |i, k| {
+            edge_eq(g1.edge_weight(edges1[i]), g2.edge_weight(edges2[k]))
+        })
+        .unwrap();
+        edges.extend(edges1.iter().zip(pairs).map(|(e, k)| (*e, edges2[k])));
+    }
+    Some(Isomorphism { nodes, edges })
+}
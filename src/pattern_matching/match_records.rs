@@ -0,0 +1,131 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::{Graph, GraphIoError};
+
+use super::{MatchWithRefs, PatternGraph};
+
+/// A base graph element that a pattern element has been matched to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchedElement<Weight> {
+    /// Position of the element in [Graph::nodes] or [Graph::edges] of the base graph.
+    ///
+    /// For base graphs written with [crate::formats::write_json], this is the ID of the element in the file.
+    pub id: usize,
+    /// Weight of the element.
+    pub weight: Weight,
+}
+
+/// A single match of a pattern in serializable form, as created by [match_records].
+///
+/// Pattern elements are identified by their position in [Graph::nodes] or [Graph::edges] of the pattern,
+/// i.e. in the order they were added. Hidden pattern elements don't appear in matches.
+///
+/// In JSON, a match looks like this:
+///
+/// ```json
+/// {
+///   "nodes": { "0": { "id": 4, "weight": "Ulm" }, "1": { "id": 7, "weight": "Neu-Ulm" } },
+///   "edges": { "0": { "id": 12, "weight": 3 } }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchRecord<NodeWeight, EdgeWeight> {
+    /// Matched base graph nodes, by position of the pattern node.
+    pub nodes: BTreeMap<usize, MatchedElement<NodeWeight>>,
+    /// Matched base graph edges, by position of the pattern edge.
+    pub edges: BTreeMap<usize, MatchedElement<EdgeWeight>>,
+}
+
+/// Converts `matches` of `pattern_graph` in `base_graph` into [MatchRecord]s, borrowing the matched weights.
+///
+/// `matches` are the results of [crate::pattern_matching::solve_vf_with_refs], whose
+/// [crate::pattern_matching::MatchedRefs] identify the matched elements.
+/// The positions of the matched elements are found in one pass over the nodes and edges of `base_graph`.
+///
+/// # Panics:
+/// Panics if a matched element isn't part of `base_graph`.
+pub fn match_records<'a, N, E, P, B>(
+    pattern_graph: &P,
+    base_graph: &'a B,
+    matches: &[MatchWithRefs<'a, N, E, P, B>],
+) -> Vec<MatchRecord<&'a N, &'a E>>
+where
+    P: PatternGraph<N, E>,
+    B: Graph<N, E>,
+{
+    let pattern_nodes: HashMap<_, _> = pattern_graph
+        .nodes()
+        .enumerate()
+        .map(|(i, n)| (n, i))
+        .collect();
+    let pattern_edges: HashMap<_, _> = pattern_graph
+        .edges()
+        .enumerate()
+        .map(|(i, e)| (e, i))
+        .collect();
+    let node_ids: HashMap<_, _> = base_graph
+        .nodes()
+        .enumerate()
+        .map(|(i, m)| (m, i))
+        .collect();
+    let edge_ids: HashMap<_, _> = base_graph
+        .edges()
+        .enumerate()
+        .map(|(i, e)| (e, i))
+        .collect();
+
+    matches
+        .iter()
+        .map(|(_, matched)| MatchRecord {
+            nodes: matched
+                .nodes
+                .iter()
+                .map(|(n, m)| {
+                    let id = node_ids
+                        .get(m)
+                        .expect("Couldn't find matched node: Match is not from this base graph.");
+                    let weight = base_graph.node_weight(*m);
+                    (pattern_nodes[n], MatchedElement { id: *id, weight })
+                })
+                .collect(),
+            edges: matched
+                .edges
+                .iter()
+                .map(|(e, e2)| {
+                    let id = edge_ids
+                        .get(e2)
+                        .expect("Couldn't find matched edge: Match is not from this base graph.");
+                    let weight = base_graph.edge_weight(*e2);
+                    (pattern_edges[e], MatchedElement { id: *id, weight })
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Writes `matches` of `pattern_graph` in `base_graph` to `writer` as a single JSON document,
+/// holding an array with the [MatchRecord] of each match.
+///
+/// # Panics:
+/// Panics if a matched element isn't part of `base_graph`, see [match_records].
+pub fn write_matches_json<'a, N, E, P, B, W>(
+    pattern_graph: &P,
+    base_graph: &'a B,
+    matches: &[MatchWithRefs<'a, N, E, P, B>],
+    writer: W,
+) -> Result<(), GraphIoError>
+where
+    N: Serialize,
+    E: Serialize,
+    P: PatternGraph<N, E>,
+    B: Graph<N, E>,
+    W: Write,
+{
+    let records = match_records(pattern_graph, base_graph, matches);
+    serde_json::to_writer(writer, &records).map_err(GraphIoError::from)
+}
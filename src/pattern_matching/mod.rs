@@ -14,11 +14,23 @@
 //! [pattern_matching::new_pattern] and passing that pattern to
 //! [pattern_matching::solve_vf]. Conditions in the pattern graph can be either constructed as function closures or with the [matcher] macro.
 //!
+//! On large base graphs, [pattern_matching::solve_vf_indexed] allows to start the search from
+//! the nodes found in a [graph::PropertyIndex] instead of trying every base graph node.
+//!
+//! [pattern_matching::is_isomorphic] uses the same algorithm to check whether two whole graphs are isomorphic,
+//! e.g. to compare results found by different queries.
+//!
+//! [pattern_matching::solve_vf_with_refs] additionally returns the base graph elements each match has been found at.
+//! To persist or ship the results of a query, [pattern_matching::write_matches_json] writes these matches
+//! into one JSON document, identifying the matched elements by their position in the base graph.
+//!
 //! For examples see the unit tests for this module (located in the `tests` folder of the crate source).
 
+use std::{collections::HashMap, hash::Hash};
+
 use vf_algorithms::VfState;
 
-use crate::graph::Graph;
+use crate::graph::{Graph, PropertyIndex};
 
 /// Module that contains an implementation for subgraph algorithms.
 ///
@@ -37,6 +49,14 @@ pub use pattern::*;
 mod algorithm;
 pub use algorithm::*;
 
+/// Isomorphism checks between whole graphs.
+mod isomorphism;
+pub use isomorphism::*;
+
+/// Serializable records of matches.
+mod match_records;
+pub use match_records::*;
+
 /// Creates an empty new graph pattern.
 pub fn new_pattern<NodeWeight, EdgeWeight>() -> impl PatternGraph<NodeWeight, EdgeWeight> {
     petgraph::Graph::new()
@@ -56,3 +76,44 @@ where
 {
     VfState::eval(pattern_graph, base_graph)
 }
+
+/// Solve a graph matching problem instance like [solve_vf], also returning the base graph elements
+/// that the elements of each [MatchedGraph] have been matched to.
+///
+/// The [MatchedRefs] allow to look at the matched elements in `base_graph` itself,
+/// e.g. to inspect their surroundings with [crate::filter_map::FilterMap::match_context].
+pub fn solve_vf_with_refs<'a, N, E, Pattern, Base>(
+    pattern_graph: &'a Pattern,
+    base_graph: &'a Base,
+) -> Vec<MatchWithRefs<'a, N, E, Pattern, Base>>
+where
+    Pattern: PatternGraph<N, E>,
+    Base: Graph<N, E>,
+{
+    VfState::eval_with_refs(pattern_graph, base_graph, HashMap::new())
+}
+
+/// Solve a graph matching problem instance like [solve_vf], using `index` to find the candidates for some pattern nodes.
+///
+/// `lookups` assigns index keys to pattern nodes. Each of those pattern nodes is only
+/// matched to base graph nodes stored under its key in `index`. Instead of trying every base graph node
+/// for the first pattern node, the search then starts from the indexed candidates.
+///
+/// `index` must have been built from `base_graph`.
+pub fn solve_vf_indexed<'a, N, E, K, Pattern, Base>(
+    pattern_graph: &'a Pattern,
+    base_graph: &'a Base,
+    index: &PropertyIndex<K, Base::NodeRef>,
+    lookups: &HashMap<Pattern::NodeRef, K>,
+) -> Vec<MatchedGraph<'a, N, E, Pattern>>
+where
+    Pattern: PatternGraph<N, E>,
+    Base: Graph<N, E>,
+    K: Eq + Hash,
+{
+    let candidates = lookups
+        .iter()
+        .map(|(n, key)| (*n, index.get(key).iter().copied().collect()))
+        .collect();
+    VfState::eval_with_candidates(pattern_graph, base_graph, candidates)
+}
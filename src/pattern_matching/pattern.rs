@@ -72,7 +72,7 @@ pub trait PatternGraph<NodeWeight, EdgeWeight>:
     /// 1. `from`, the source node of the new edge.
     /// 2. `to`, the destination node.
     /// 3. `condition`, a function to test if an edge in a base graph matches
-    /// what we want in the pattern graph.
+    ///    what we want in the pattern graph.
     ///
     /// ## Output:
     /// An edge reference.
@@ -95,7 +95,7 @@ pub trait PatternGraph<NodeWeight, EdgeWeight>:
     /// 1. `from`, the source node of the new edge.
     /// 2. `to`, the destination node.
     /// 3. `condition`, a function to test if an edge in a base graph matches
-    /// what we want in the pattern graph.
+    ///    what we want in the pattern graph.
     ///
     /// ## Output:
     /// An edge reference.
@@ -9,8 +9,10 @@ use bimap::BiHashMap;
 
 use crate::filter_map::FilterMap;
 use crate::{
-    graph::{incoming_nodes, outgoing_nodes, Graph},
-    pattern_matching::{MatchedGraph, PatternElement, PatternGraph, SubgraphAlgorithm},
+    graph::{incoming_nodes, opposite_node, outgoing_nodes, Graph},
+    pattern_matching::{
+        MatchWithRefs, MatchedGraph, MatchedRefs, PatternElement, PatternGraph, SubgraphAlgorithm,
+    },
 };
 
 /// Implements an subgraph isomorphism algorithm based on the papers
@@ -20,7 +22,7 @@ use crate::{
 /// "Performance Evaluation of the VF Graph Matching Algorithm"
 /// by the same authors in 1999 (doi 10.1109/ICIAP.1999.797762).
 /// The paper referenced above call this algorithm VF2 respectively VF.
-
+///
 /// VfState defines the required data structures as defined in Subsection 2.4
 /// of the 2004 paper, as well as the algorithms to run them.
 pub struct VfState<
@@ -45,6 +47,8 @@ pub struct VfState<
     base_graph: &'a B,
     /// Vec of found graphs we may return.
     results: Vec<MatchedGraph<'a, NodeWeight, EdgeWeight, P>>,
+    /// Base graph elements of the found graphs, in the same order as `results`.
+    matched_refs: Vec<MatchedRefs<NRef, ERef, N2Ref, E2Ref>>,
 
     /// Matching of nodes in `pattern_graph` to suitable nodes in `base_graph`.
     /// `core[n] = m` says that the node `n` can be matched to node `m`.
@@ -67,6 +71,20 @@ pub struct VfState<
 
     /// Counter for how many nodes we actually need to return.
     nodes_to_take: usize,
+
+    /// Restricts pattern nodes to the given sets of base graph nodes, usually
+    /// obtained from a [crate::graph::PropertyIndex]. Pattern nodes without an entry may be
+    /// matched to any base node.
+    ///
+    /// We start the search from such a node, instead of trying every base node.
+    candidates: HashMap<NRef, HashSet<N2Ref>>,
+
+    /// Number of results after which the search stops.
+    max_results: usize,
+
+    /// Number of distinct predecessors and successors of each pattern node.
+    /// Nodes matched to them must have at least as many incoming and outgoing edges.
+    neighbor_counts: HashMap<NRef, (usize, usize)>,
 }
 
 /// Implementation of VfState/the VF2 Algorithm.
@@ -85,25 +103,26 @@ where
     /// This ordering ensures that:
     ///
     /// 1. We process nodes in the result before ignored ones.
-    /// 2. We follow the given ordering of the node indices.
+    /// 2. We process nodes with restricted candidates before the others.
+    /// 3. We follow the given ordering of the node indices.
     ///
     /// We use this method to ensure set semantics.
     fn give_node_order(&self, n1: NRef, n2: NRef) -> Ordering {
         let n1_appears = self.pattern_graph.node_weight(n1).should_appear();
         let n2_appears = self.pattern_graph.node_weight(n2).should_appear();
-        if n1_appears && !n2_appears {
-            Ordering::Less
-        } else if !n1_appears && n2_appears {
-            Ordering::Greater
-        } else {
-            n1.cmp(&n2)
-        }
+        let n1_restricted = self.candidates.contains_key(&n1);
+        let n2_restricted = self.candidates.contains_key(&n2);
+        n2_appears
+            .cmp(&n1_appears)
+            .then(n2_restricted.cmp(&n1_restricted))
+            .then(n1.cmp(&n2))
     }
 
     /// Returns a tuple (N, N2) of node references.
     /// N contains the smallest unmatched node within the pattern graph,
     /// and N2 unmatched nodes within the base graph.
     /// When matched nodes contain a successor, we use another method.
+    /// If N has restricted candidates, N2 only contains those.
     ///
     /// This ordering is described in the 1999 first paper.
     fn find_unmatched_unconnected_nodes(&'a self) -> (Option<NRef>, Vec<N2Ref>) {
@@ -113,11 +132,18 @@ where
             .filter(|n| !self.core.contains_left(n))
             .min_by(|n1, n2| self.give_node_order(*n1, *n2));
 
-        let base_nodes: Vec<_> = self
-            .base_graph
-            .nodes()
-            .filter(|n| !self.core.contains_right(n))
-            .collect();
+        let base_nodes: Vec<_> = match n.and_then(|n| self.candidates.get(&n)) {
+            Some(candidates) => candidates
+                .iter()
+                .copied()
+                .filter(|m| !self.core.contains_right(m))
+                .collect(),
+            None => self
+                .base_graph
+                .nodes()
+                .filter(|m| !self.core.contains_right(m))
+                .collect(),
+        };
 
         (n, base_nodes)
     }
@@ -186,19 +212,39 @@ where
     /// these conditions:
     ///
     /// ### Syntactic:
-    /// 1. `check_predecessor_relation`
-    /// 2. `check_successor_relation`
+    /// 1. `check_candidates`
+    /// 2. `check_degrees`
+    /// 3. `check_predecessor_relation`
+    /// 4. `check_successor_relation`
     ///
     /// ### Semantic:
     /// 1. `check_node_semantics`
     /// 2. `check_edge_semantics`
     fn is_valid_matching(&self, n: NRef, m: N2Ref) -> bool {
-        self.check_node_semantics(n, m)
+        self.check_candidates(n, m)
+            && self.check_degrees(n, m)
+            && self.check_node_semantics(n, m)
             && self.check_predecessor_relation(n, m)
             && self.check_successor_relation(n, m)
             && self.check_edge_semantics(n, m)
     }
 
+    /// Test that m is among the candidates of n, if those are restricted.
+    fn check_candidates(&self, n: NRef, m: N2Ref) -> bool {
+        self.candidates
+            .get(&n)
+            .is_none_or(|candidates| candidates.contains(&m))
+    }
+
+    /// Test that m has at least as many incoming and outgoing edges as n has distinct
+    /// predecessors and successors, since those must be matched to distinct neighbors of m.
+    /// Parallel edges of n may be matched to the same edge of m, so they aren't counted.
+    /// Cheap for most storage backends, this rules out many candidates early.
+    fn check_degrees(&self, n: NRef, m: N2Ref) -> bool {
+        let (preds, succs) = self.neighbor_counts[&n];
+        self.base_graph.in_degree(m) >= preds && self.base_graph.out_degree(m) >= succs
+    }
+
     /// Test that assigning n to m leaves the predecessor relations intact:
     /// We may map any matched predecessor n' of n in `pattern_graph` to
     /// another matched node m' that precedes m in `base_graph`.
@@ -254,50 +300,64 @@ where
     /// Consider all edges e that lead to and from n. Take those edges for
     /// which we already established a matching to another node m.
     fn check_edge_semantics(&self, n: NRef, m: N2Ref) -> bool {
+        // Map successor edges of m to their matched outgoing nodes.
+        let m_succs_matched = self.group_matched_edges(self.base_graph.outgoing_edges(m), m);
+        // Map predecessor edges of m to their matched incoming nodes.
+        let m_preds_matched = self.group_matched_edges(self.base_graph.incoming_edges(m), m);
+
         // Take successor edges of n that have been matched.
         let n_succs_matched = self
             .pattern_graph
             .outgoing_edges(n)
-            .map(|e| (self.pattern_graph.adjacent_nodes(e).1, e))
-            .filter(|(n_succ, _)| self.core.contains_left(n_succ));
-
-        // Map successor edges of m to their outgoing nodes.
-        let m_succs_matched: HashMap<N2Ref, E2Ref> = self
-            .base_graph
-            .outgoing_edges(m)
-            .map(|e| (self.base_graph.adjacent_nodes(e).1, e))
-            .filter(|(m_succ, _)| self.core.contains_right(m_succ))
-            .collect();
-
-        // Map successor edges.
-        let n_m_succ_edges = n_succs_matched
-            .map(|(n_succ, e)| (e, m_succs_matched[self.core.get_by_left(&n_succ).unwrap()]));
-
+            .map(|e| (opposite_node(self.pattern_graph, e, n), e))
+            .filter(|(n_succ, _)| self.core.contains_left(n_succ))
+            .map(|(n_succ, e)| (n_succ, e, &m_succs_matched));
         // Take predecessor edges of n that have been matched.
         let n_preds_matched = self
             .pattern_graph
             .incoming_edges(n)
-            .map(|e| (self.pattern_graph.adjacent_nodes(e).0, e))
-            .filter(|(n_pred, _)| self.core.contains_left(n_pred));
-
-        // Map predecessor edges of m to their incoming nodes.
-        let m_preds_matched: HashMap<N2Ref, E2Ref> = self
-            .base_graph
-            .incoming_edges(m)
-            .map(|e| (self.base_graph.adjacent_nodes(e).0, e))
-            .filter(|(m_pred, _)| self.core.contains_right(m_pred))
-            .collect();
+            .map(|e| (opposite_node(self.pattern_graph, e, n), e))
+            .filter(|(n_pred, _)| self.core.contains_left(n_pred))
+            .map(|(n_pred, e)| (n_pred, e, &m_preds_matched));
+
+        // All successor edges in pattern_graph have a counterpart in base_graph that conforms to
+        // their specification, and so do the predecessors.
+        n_succs_matched
+            .chain(n_preds_matched)
+            .all(|(n_other, e, m_matched)| {
+                let m_other = self.core.get_by_left(&n_other).unwrap();
+                m_matched
+                    .get(m_other)
+                    .is_some_and(|candidates| self.find_matching_edge(e, candidates).is_some())
+            })
+    }
 
-        // Map predecessor edges.
-        let n_m_pred_edges = n_preds_matched
-            .map(|(n_pred, e)| (e, m_preds_matched[self.core.get_by_left(&n_pred).unwrap()]));
+    /// Groups the given edges adjacent to m by their opposite node,
+    /// keeping only those edges that lead to matched nodes.
+    fn group_matched_edges(
+        &self,
+        edges: impl Iterator<Item = E2Ref>,
+        m: N2Ref,
+    ) -> HashMap<N2Ref, Vec<E2Ref>> {
+        let mut grouped: HashMap<N2Ref, Vec<E2Ref>> = HashMap::new();
+        edges
+            .map(|e2| (opposite_node(self.base_graph, e2, m), e2))
+            .filter(|(m_other, _)| self.core.contains_right(m_other))
+            .for_each(|(m_other, e2)| grouped.entry(m_other).or_default().push(e2));
+        grouped
+    }
 
-        // All successor edges in base_graph conform to the specification in pattern_graph,
-        // and so do the predecessors.
-        n_m_pred_edges.chain(n_m_succ_edges).all(|(e, e2)| {
-            let matcher = self.pattern_graph.edge_weight(e);
-            let matched = self.base_graph.edge_weight(e2);
-            matcher.may_match(matched)
+    /// Finds an edge among `candidates` in `base_graph` that pattern edge e may be matched to.
+    ///
+    /// This requires e's matcher to accept the candidate. Furthermore,
+    /// undirected edges in the pattern may only be matched to undirected edges in the base graph.
+    /// Directed edges may be matched to both directed and undirected edges.
+    fn find_matching_edge(&self, e: ERef, candidates: &[E2Ref]) -> Option<E2Ref> {
+        let matcher = self.pattern_graph.edge_weight(e);
+        let directed = self.pattern_graph.is_directed_edge(e);
+        candidates.iter().copied().find(|e2| {
+            (directed || !self.base_graph.is_directed_edge(*e2))
+                && matcher.may_match(self.base_graph.edge_weight(*e2))
         })
     }
 
@@ -343,41 +403,53 @@ where
     /// Copy the keys from pattern_graph along with the weights referred
     /// to by the depths from base_graph. Note that any elements in the result graph that
     /// are marked as ignored, will not appear in the result.
+    ///
+    /// The matched base graph references are recorded in `matched_refs`.
     fn produce_graph(&mut self) {
-        // Get node references and weights.
-        let node_list = self
+        // Get node references.
+        let nodes: HashMap<_, _> = self
             .core
             .iter()
             .filter(|(n, _)| self.pattern_graph.node_weight(**n).should_appear())
-            .map(|(n, m)| (*n, self.base_graph.node_weight(*m)))
+            .map(|(n, m)| (*n, *m))
             .collect();
 
         // Mutable Edge List.
-        let mut edge_list = HashMap::new();
+        let mut edges = HashMap::new();
         // Find outgoing nodes (E, E2) of each matching and matched node pair (n, m).
         // Match each edge e from E to another e2 from E2 based on their matched successors,
         // then e to the weight associated with e2.
         for (n, m) in &self.core {
-            let n_succs = self
-                .pattern_graph
+            let m_succs = self.group_matched_edges(self.base_graph.outgoing_edges(*m), *m);
+            self.pattern_graph
                 .outgoing_edges(*n)
                 .filter(|e| self.pattern_graph.edge_weight(*e).should_appear())
-                .map(|e| (self.pattern_graph.adjacent_nodes(e).1, e));
-            let m_succs: HashMap<_, _> = self
-                .base_graph
-                .outgoing_edges(*m)
-                .map(|e2| (self.base_graph.adjacent_nodes(e2).1, e2))
-                .collect();
-            n_succs
-                .map(|(n_succ, e)| (e, m_succs[self.core.get_by_left(&n_succ).unwrap()]))
-                .map(|(e, e2)| (e, self.base_graph.edge_weight(e2)))
-                .for_each(|(e_ref, edge_weight)| {
-                    edge_list.insert(e_ref, edge_weight);
+                .map(|e| {
+                    let n_succ = opposite_node(self.pattern_graph, e, *n);
+                    let candidates = &m_succs[self.core.get_by_left(&n_succ).unwrap()];
+                    let e2 = self
+                        .find_matching_edge(e, candidates)
+                        .expect("Matched edges have been checked before.");
+                    (e, e2)
+                })
+                .for_each(|(e_ref, e2_ref)| {
+                    edges.insert(e_ref, e2_ref);
                 });
         }
 
+        // Get the weights of the matched elements.
+        let node_list = nodes
+            .iter()
+            .map(|(n, m)| (*n, self.base_graph.node_weight(*m)))
+            .collect();
+        let edge_list = edges
+            .iter()
+            .map(|(e, e2)| (*e, self.base_graph.edge_weight(*e2)))
+            .collect();
+
         let result = FilterMap::new(self.pattern_graph, node_list, edge_list);
         self.results.push(result);
+        self.matched_refs.push(MatchedRefs { nodes, edges });
     }
 
     /// Looks up subgraphs and puts them into results.
@@ -407,6 +479,9 @@ where
             // Assert we always will have a node in the pattern.
             let n = pat_node.unwrap();
             for m in base_nodes {
+                if self.results.len() >= self.max_results {
+                    break;
+                }
                 self.assign(n, m, depth);
                 // Test compatibility.
                 if self.is_valid_matching(n, m) {
@@ -431,29 +506,45 @@ where
     /// ## Input:
     /// 1. `pattern_graph`, a PatternGraph with NRef node references.
     /// 2. `base_graph`, any Graph with N2Ref node references.
+    /// 3. `candidates`, restricting pattern nodes to the given base nodes.
+    /// 4. `max_results`, the number of results after which to stop the search.
     ///
     /// ## Output:
     /// A VfState struct.
     fn init(
         pattern_graph: &'a P,
         base_graph: &'a B,
+        candidates: HashMap<NRef, HashSet<N2Ref>>,
+        max_results: usize,
     ) -> VfState<'a, NodeWeight, EdgeWeight, NRef, ERef, N2Ref, E2Ref, P, B> {
         // Count the number of nodes to not ignore.
         let nodes_to_take = pattern_graph
             .nodes()
             .filter(|n| pattern_graph.node_weight(*n).should_appear())
             .count();
+        let neighbor_counts = pattern_graph
+            .nodes()
+            .map(|n| {
+                let preds: HashSet<_> = incoming_nodes(pattern_graph, n).collect();
+                let succs: HashSet<_> = outgoing_nodes(pattern_graph, n).collect();
+                (n, (preds.len(), succs.len()))
+            })
+            .collect();
 
         VfState {
             pattern_graph,
             base_graph,
             results: vec![],
+            matched_refs: vec![],
             core: BiHashMap::new(),
             out_1: HashMap::new(),
             out_2: HashMap::new(),
             in_1: HashMap::new(),
             in_2: HashMap::new(),
             nodes_to_take,
+            candidates,
+            max_results,
+            neighbor_counts,
         }
     }
 
@@ -469,6 +560,51 @@ where
         }
         let _ = self.find_subgraphs(0);
     }
+
+    /// Like [SubgraphAlgorithm::eval], but only matches the pattern nodes
+    /// that are keys of `candidates` to the base graph nodes listed for them.
+    ///
+    /// Passing a small set of candidates for at least one pattern node
+    /// saves trying every base graph node when starting the search.
+    pub fn eval_with_candidates(
+        pattern_graph: &'a P,
+        base_graph: &'a B,
+        candidates: HashMap<NRef, HashSet<N2Ref>>,
+    ) -> Vec<MatchedGraph<'a, NodeWeight, EdgeWeight, P>> {
+        let mut vfstate = VfState::init(pattern_graph, base_graph, candidates, usize::MAX);
+        vfstate.run_query();
+
+        // Move results out of vstate struct before dropping it.
+        std::mem::take(&mut vfstate.results)
+    }
+
+    /// Like [VfState::eval_with_candidates], but also returns the base graph elements
+    /// that each result has been matched to.
+    pub fn eval_with_refs(
+        pattern_graph: &'a P,
+        base_graph: &'a B,
+        candidates: HashMap<NRef, HashSet<N2Ref>>,
+    ) -> Vec<MatchWithRefs<'a, NodeWeight, EdgeWeight, P, B>> {
+        let mut vfstate = VfState::init(pattern_graph, base_graph, candidates, usize::MAX);
+        vfstate.run_query();
+
+        // Move results out of vstate struct before dropping it.
+        let results = std::mem::take(&mut vfstate.results);
+        let matched_refs = std::mem::take(&mut vfstate.matched_refs);
+        results.into_iter().zip(matched_refs).collect()
+    }
+
+    /// Like [SubgraphAlgorithm::eval], but stops the search as soon as a match has been found.
+    ///
+    /// Returns None if there is no match.
+    pub fn eval_first(
+        pattern_graph: &'a P,
+        base_graph: &'a B,
+    ) -> Option<MatchedGraph<'a, NodeWeight, EdgeWeight, P>> {
+        let mut vfstate = VfState::init(pattern_graph, base_graph, HashMap::new(), 1);
+        vfstate.run_query();
+        vfstate.results.pop()
+    }
 }
 
 impl<'a, NodeWeight, EdgeWeight, NRef, ERef, N2Ref, E2Ref, P, B>
@@ -495,10 +631,6 @@ where
             P,
         >,
     > {
-        let mut vfstate = VfState::init(pattern_graph, base_graph);
-        vfstate.run_query();
-
-        // Move results out of vstate struct before dropping it.
-        std::mem::take(&mut vfstate.results)
+        VfState::eval_with_candidates(pattern_graph, base_graph, HashMap::new())
     }
 }
@@ -1,7 +1,7 @@
-use crate::graph::GraphReadWriter;
-use petgraph::stable_graph::DefaultIx;
+use crate::graph::{GraphIoError, GraphReadWriter};
+use petgraph::stable_graph::{DefaultIx, StableGraph};
 use serde::{de::DeserializeOwned, Serialize};
-use std::{fs::File, io::Error as IOError};
+use std::io::{Read, Write};
 
 /// Implementation of GraphReadWriter trait using serde_json.
 /// Nodes and Edges need to implement Serializable and Deserializable
@@ -13,20 +13,42 @@ where
     EdgeWeight: Serialize + DeserializeOwned,
     EdgeType: petgraph::EdgeType,
 {
-    /// Serializes the graph to JSON. This overwrites the file given under path.
-    /// If serde_json fails, packs the underlying error in an std::io::Error for examination.
-    fn serialize_graph_to_file(&self, path: &str) -> Result<(), IOError> {
-        let file = File::create(path)?;
-        serde_json::ser::to_writer(file, &self)
-            .map_err(|e| IOError::new(std::io::ErrorKind::Other, e))
+    /// Serializes the graph to JSON.
+    /// Errors of serde_json are converted into [GraphIoError]s, keeping the position of syntax errors.
+    fn write_graph<W: Write>(&self, writer: W) -> Result<(), GraphIoError> {
+        serde_json::ser::to_writer(writer, &self).map_err(GraphIoError::from)
     }
 
     /// Deserializes a graph stored as JSON, and packs it into a Box.
-    /// If serde_json fails, packs the underlying error in an std::io::Error for examination.
-    fn deserialize_graph_from_file(path: &str) -> Result<Box<Self>, IOError> {
-        let file = File::open(path)?;
-        serde_json::de::from_reader(file)
+    /// Errors of serde_json are converted into [GraphIoError]s, keeping the position of syntax errors.
+    fn read_graph<R: Read>(reader: R) -> Result<Box<Self>, GraphIoError> {
+        serde_json::de::from_reader(reader)
             .map(Box::new)
-            .map_err(|e| IOError::new(std::io::ErrorKind::Other, e))
+            .map_err(GraphIoError::from)
+    }
+}
+
+/// Implementation of GraphReadWriter trait for StableGraph using serde_json.
+/// Vacant node and edge slots are stored as well, so indices remain
+/// stable across serialization.
+impl<NodeWeight, EdgeWeight, EdgeType> GraphReadWriter<NodeWeight, EdgeWeight>
+    for StableGraph<NodeWeight, EdgeWeight, EdgeType, DefaultIx>
+where
+    NodeWeight: Serialize + DeserializeOwned,
+    EdgeWeight: Serialize + DeserializeOwned,
+    EdgeType: petgraph::EdgeType,
+{
+    /// Serializes the graph to JSON.
+    /// Errors of serde_json are converted into [GraphIoError]s, keeping the position of syntax errors.
+    fn write_graph<W: Write>(&self, writer: W) -> Result<(), GraphIoError> {
+        serde_json::ser::to_writer(writer, &self).map_err(GraphIoError::from)
+    }
+
+    /// Deserializes a graph stored as JSON, and packs it into a Box.
+    /// Errors of serde_json are converted into [GraphIoError]s, keeping the position of syntax errors.
+    fn read_graph<R: Read>(reader: R) -> Result<Box<Self>, GraphIoError> {
+        serde_json::de::from_reader(reader)
+            .map(Box::new)
+            .map_err(GraphIoError::from)
     }
 }
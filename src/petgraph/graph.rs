@@ -1,6 +1,12 @@
+use std::fmt::Debug;
+use std::hash::BuildHasher;
+
 use petgraph::graph::EdgeIndex;
 use petgraph::graph::NodeIndex;
-use petgraph::visit::EdgeRef;
+use petgraph::data::DataMap;
+use petgraph::graphmap::{GraphMap, NodeTrait};
+use petgraph::stable_graph::StableGraph;
+use petgraph::visit::{EdgeRef, IntoNodeReferences};
 use petgraph::Direction::Incoming;
 use petgraph::Direction::Outgoing;
 
@@ -65,6 +71,26 @@ where
             .expect("Couldn't find edge weight: Edge reference invalid.")
     }
 
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        node.index() < self.node_count()
+    }
+
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        edge.index() < self.edge_count()
+    }
+
+    fn try_adjacent_nodes(&self, edge: Self::EdgeRef) -> Option<(Self::NodeRef, Self::NodeRef)> {
+        self.edge_endpoints(edge)
+    }
+
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        petgraph::graph::Graph::node_weight(self, node)
+    }
+
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        petgraph::graph::Graph::edge_weight(self, edge)
+    }
+
     fn node_weights(&self) -> Self::NodeWeightsIterator<'_> {
         petgraph::graph::Graph::node_weights(self)
     }
@@ -98,3 +124,241 @@ where
         self.node_count()
     }
 }
+
+/// Implementation for petgraph's StableGraph type.
+///
+/// Unlike [`petgraph::graph::Graph`], removing nodes or edges from a StableGraph
+/// does not invalidate the indices of the remaining elements, so node and edge
+/// references obtained through this trait stay valid across removals.
+/// Valid indices may thus contain gaps.
+impl<NodeWeight, EdgeWeight, Direction, IndexType> Graph<NodeWeight, EdgeWeight>
+    for StableGraph<NodeWeight, EdgeWeight, Direction, IndexType>
+where
+    IndexType: petgraph::graph::IndexType,
+    Direction: petgraph::EdgeType,
+{
+    type NodeRef = NodeIndex<IndexType>;
+    type EdgeRef = EdgeIndex<IndexType>;
+    fn is_directed(&self) -> bool {
+        StableGraph::is_directed(self)
+    }
+
+    fn is_directed_edge(&self, edge: Self::EdgeRef) -> bool {
+        assert!(StableGraph::edge_weight(self, edge).is_some());
+        // petgraph doesn't support mixing directed and undirected edges.
+        self.is_directed()
+    }
+
+    type AdjacentEdgesIterator<'a> = impl Iterator<Item = Self::EdgeRef> + 'a where Self: 'a;
+    fn adjacent_edges(&self, node: Self::NodeRef) -> Self::AdjacentEdgesIterator<'_> {
+        self.edges_directed(node, Incoming)
+            .chain(
+                self.edges_directed(node, Outgoing)
+                    .filter(|_| self.is_directed()),
+            )
+            .map(|e| e.id())
+    }
+
+    type IncomingEdgesIterator<'a> = impl Iterator<Item = Self::EdgeRef> + 'a where Self: 'a;
+    fn incoming_edges(&self, node: Self::NodeRef) -> Self::IncomingEdgesIterator<'_> {
+        self.edges_directed(node, Incoming).map(|e| e.id())
+    }
+
+    type OutgoingEdgesIterator<'a> = impl Iterator<Item = Self::EdgeRef> + 'a where Self: 'a;
+    fn outgoing_edges(&self, node: Self::NodeRef) -> Self::OutgoingEdgesIterator<'_> {
+        self.edges_directed(node, Outgoing).map(|e| e.id())
+    }
+
+    fn adjacent_nodes(&self, edge: Self::EdgeRef) -> (Self::NodeRef, Self::NodeRef) {
+        self.edge_endpoints(edge)
+            .expect("Couldn't find edge endpoint references: Edge reference invalid.")
+    }
+
+    fn node_weight(&self, node: Self::NodeRef) -> &NodeWeight {
+        StableGraph::node_weight(self, node)
+            .expect("Couldn't find node weight: Node reference invalid.")
+    }
+
+    fn edge_weight(&self, edge: Self::EdgeRef) -> &EdgeWeight {
+        StableGraph::edge_weight(self, edge)
+            .expect("Couldn't find edge weight: Edge reference invalid.")
+    }
+
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        StableGraph::contains_node(self, node)
+    }
+
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        StableGraph::edge_weight(self, edge).is_some()
+    }
+
+    fn try_adjacent_nodes(&self, edge: Self::EdgeRef) -> Option<(Self::NodeRef, Self::NodeRef)> {
+        self.edge_endpoints(edge)
+    }
+
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        StableGraph::node_weight(self, node)
+    }
+
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        StableGraph::edge_weight(self, edge)
+    }
+
+    fn node_weights(&self) -> Self::NodeWeightsIterator<'_> {
+        StableGraph::node_weights(self)
+    }
+
+    fn edge_weights(&self) -> Self::EdgeWeightsIterator<'_> {
+        StableGraph::edge_weights(self)
+    }
+
+    type NodesIterator<'a> = impl Iterator<Item = Self::NodeRef> + 'a where Self: 'a;
+    fn nodes(&self) -> Self::NodesIterator<'_> {
+        // Skips the vacant slots left behind by removed nodes.
+        self.node_indices()
+    }
+
+    type EdgesIterator<'a> = impl Iterator<Item = Self::EdgeRef> + 'a where Self: 'a;
+    fn edges(&self) -> Self::EdgesIterator<'_> {
+        self.edge_indices()
+    }
+
+    type NodeWeightsIterator<'a>
+     = impl Iterator<Item = &'a NodeWeight> + 'a where Self: 'a, NodeWeight: 'a;
+
+    type EdgeWeightsIterator<'a>
+     = impl Iterator<Item = &'a EdgeWeight> + 'a where Self: 'a, EdgeWeight: 'a;
+
+    fn count_edges(&self) -> usize {
+        self.edge_count()
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.node_count()
+    }
+}
+
+/// Implementation for petgraph's GraphMap type, where nodes are identified by their keys.
+///
+/// The node keys act both as node references and as node weights. Since GraphMap
+/// does not allow parallel edges, an edge is referenced by the pair of its endpoint keys.
+/// For undirected graphs, that pair is normalized so that the smaller key comes first.
+impl<NodeWeight, EdgeWeight, Direction, S> Graph<NodeWeight, EdgeWeight>
+    for GraphMap<NodeWeight, EdgeWeight, Direction, S>
+where
+    NodeWeight: NodeTrait + Debug,
+    Direction: petgraph::EdgeType,
+    S: BuildHasher,
+{
+    type NodeRef = NodeWeight;
+    type EdgeRef = (NodeWeight, NodeWeight);
+    fn is_directed(&self) -> bool {
+        GraphMap::is_directed(self)
+    }
+
+    fn is_directed_edge(&self, edge: Self::EdgeRef) -> bool {
+        assert!(GraphMap::contains_edge(self, edge.0, edge.1));
+        // petgraph doesn't support mixing directed and undirected edges.
+        self.is_directed()
+    }
+
+    type AdjacentEdgesIterator<'a> = impl Iterator<Item = Self::EdgeRef> + 'a where Self: 'a;
+    fn adjacent_edges(&self, node: Self::NodeRef) -> Self::AdjacentEdgesIterator<'_> {
+        self.edges_directed(node, Incoming)
+            .chain(
+                self.edges_directed(node, Outgoing)
+                    .filter(|_| self.is_directed()),
+            )
+            .map(|(a, b, _)| graph_map_edge::<_, Direction>(a, b))
+    }
+
+    type IncomingEdgesIterator<'a> = impl Iterator<Item = Self::EdgeRef> + 'a where Self: 'a;
+    fn incoming_edges(&self, node: Self::NodeRef) -> Self::IncomingEdgesIterator<'_> {
+        self.edges_directed(node, Incoming)
+            .map(|(a, b, _)| graph_map_edge::<_, Direction>(a, b))
+    }
+
+    type OutgoingEdgesIterator<'a> = impl Iterator<Item = Self::EdgeRef> + 'a where Self: 'a;
+    fn outgoing_edges(&self, node: Self::NodeRef) -> Self::OutgoingEdgesIterator<'_> {
+        self.edges_directed(node, Outgoing)
+            .map(|(a, b, _)| graph_map_edge::<_, Direction>(a, b))
+    }
+
+    fn adjacent_nodes(&self, edge: Self::EdgeRef) -> (Self::NodeRef, Self::NodeRef) {
+        if !GraphMap::contains_edge(self, edge.0, edge.1) {
+            panic!("Couldn't find edge endpoint references: Edge reference invalid.")
+        }
+        edge
+    }
+
+    fn node_weight(&self, node: Self::NodeRef) -> &NodeWeight {
+        self.try_node_weight(node)
+            .expect("Couldn't find node weight: Node reference invalid.")
+    }
+
+    fn edge_weight(&self, edge: Self::EdgeRef) -> &EdgeWeight {
+        GraphMap::edge_weight(self, edge.0, edge.1)
+            .expect("Couldn't find edge weight: Edge reference invalid.")
+    }
+
+    fn contains_node(&self, node: Self::NodeRef) -> bool {
+        GraphMap::contains_node(self, node)
+    }
+
+    fn contains_edge(&self, edge: Self::EdgeRef) -> bool {
+        GraphMap::contains_edge(self, edge.0, edge.1)
+    }
+
+    fn try_node_weight(&self, node: Self::NodeRef) -> Option<&NodeWeight> {
+        // The weight of a node is its key, borrowed from where the graph stores it.
+        DataMap::node_weight(self, node)
+    }
+
+    fn try_edge_weight(&self, edge: Self::EdgeRef) -> Option<&EdgeWeight> {
+        GraphMap::edge_weight(self, edge.0, edge.1)
+    }
+
+    fn node_weights(&self) -> Self::NodeWeightsIterator<'_> {
+        self.node_references().map(|(_, weight)| weight)
+    }
+
+    fn edge_weights(&self) -> Self::EdgeWeightsIterator<'_> {
+        self.all_edges().map(|(_, _, weight)| weight)
+    }
+
+    type NodesIterator<'a> = impl Iterator<Item = Self::NodeRef> + 'a where Self: 'a;
+    fn nodes(&self) -> Self::NodesIterator<'_> {
+        GraphMap::nodes(self)
+    }
+
+    type EdgesIterator<'a> = impl Iterator<Item = Self::EdgeRef> + 'a where Self: 'a;
+    fn edges(&self) -> Self::EdgesIterator<'_> {
+        self.all_edges().map(|(a, b, _)| (a, b))
+    }
+
+    type NodeWeightsIterator<'a>
+     = impl Iterator<Item = &'a NodeWeight> + 'a where Self: 'a, NodeWeight: 'a;
+
+    type EdgeWeightsIterator<'a>
+     = impl Iterator<Item = &'a EdgeWeight> + 'a where Self: 'a, EdgeWeight: 'a;
+
+    fn count_edges(&self) -> usize {
+        self.edge_count()
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.node_count()
+    }
+}
+
+/// Builds the edge reference for the GraphMap edge between `a` and `b`.
+///
+/// Undirected GraphMaps store each edge only once under its sorted key pair,
+/// so we use the same pair as reference regardless of the direction we found the edge from.
+pub(super) fn graph_map_edge<N: NodeTrait, Direction: petgraph::EdgeType>(a: N, b: N) -> (N, N) {
+    if Direction::is_directed() || a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
@@ -0,0 +1,140 @@
+use std::{fmt::Debug, hash::BuildHasher};
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::graphmap::{GraphMap, NodeTrait};
+use petgraph::stable_graph::StableGraph;
+
+use crate::graph::GraphMut;
+
+use super::graph::graph_map_edge;
+
+/// GraphMut implementation for petgraph's Graph type.
+///
+/// Note that petgraph keeps node and edge indices contiguous: Removing a node or edge
+/// moves the last node respectively edge into the freed index, invalidating its
+/// previous reference. Use a StableGraph if references must survive removals.
+impl<NodeWeight, EdgeWeight, Direction, IndexType> GraphMut<NodeWeight, EdgeWeight>
+    for petgraph::graph::Graph<NodeWeight, EdgeWeight, Direction, IndexType>
+where
+    IndexType: petgraph::graph::IndexType,
+    Direction: petgraph::EdgeType,
+{
+    fn add_node(&mut self, weight: NodeWeight) -> NodeIndex<IndexType> {
+        petgraph::graph::Graph::add_node(self, weight)
+    }
+
+    fn add_edge(
+        &mut self,
+        from: NodeIndex<IndexType>,
+        to: NodeIndex<IndexType>,
+        weight: EdgeWeight,
+    ) -> EdgeIndex<IndexType> {
+        petgraph::graph::Graph::add_edge(self, from, to, weight)
+    }
+
+    fn remove_node(&mut self, node: NodeIndex<IndexType>) -> Option<NodeWeight> {
+        petgraph::graph::Graph::remove_node(self, node)
+    }
+
+    fn remove_edge(&mut self, edge: EdgeIndex<IndexType>) -> Option<EdgeWeight> {
+        petgraph::graph::Graph::remove_edge(self, edge)
+    }
+
+    fn node_weight_mut(&mut self, node: NodeIndex<IndexType>) -> &mut NodeWeight {
+        petgraph::graph::Graph::node_weight_mut(self, node)
+            .expect("Couldn't find node weight: Node reference invalid.")
+    }
+
+    fn edge_weight_mut(&mut self, edge: EdgeIndex<IndexType>) -> &mut EdgeWeight {
+        petgraph::graph::Graph::edge_weight_mut(self, edge)
+            .expect("Couldn't find edge weight: Edge reference invalid.")
+    }
+}
+
+/// GraphMut implementation for petgraph's StableGraph type.
+///
+/// References to the remaining nodes and edges stay valid when removing elements.
+impl<NodeWeight, EdgeWeight, Direction, IndexType> GraphMut<NodeWeight, EdgeWeight>
+    for StableGraph<NodeWeight, EdgeWeight, Direction, IndexType>
+where
+    IndexType: petgraph::graph::IndexType,
+    Direction: petgraph::EdgeType,
+{
+    fn add_node(&mut self, weight: NodeWeight) -> NodeIndex<IndexType> {
+        StableGraph::add_node(self, weight)
+    }
+
+    fn add_edge(
+        &mut self,
+        from: NodeIndex<IndexType>,
+        to: NodeIndex<IndexType>,
+        weight: EdgeWeight,
+    ) -> EdgeIndex<IndexType> {
+        StableGraph::add_edge(self, from, to, weight)
+    }
+
+    fn remove_node(&mut self, node: NodeIndex<IndexType>) -> Option<NodeWeight> {
+        StableGraph::remove_node(self, node)
+    }
+
+    fn remove_edge(&mut self, edge: EdgeIndex<IndexType>) -> Option<EdgeWeight> {
+        StableGraph::remove_edge(self, edge)
+    }
+
+    fn node_weight_mut(&mut self, node: NodeIndex<IndexType>) -> &mut NodeWeight {
+        StableGraph::node_weight_mut(self, node)
+            .expect("Couldn't find node weight: Node reference invalid.")
+    }
+
+    fn edge_weight_mut(&mut self, edge: EdgeIndex<IndexType>) -> &mut EdgeWeight {
+        StableGraph::edge_weight_mut(self, edge)
+            .expect("Couldn't find edge weight: Edge reference invalid.")
+    }
+}
+
+/// GraphMut implementation for petgraph's GraphMap type.
+///
+/// As nodes are identified by their keys, adding a node whose key is already
+/// present doesn't add another node, and adding an edge between two nodes that are
+/// already connected replaces the weight of the existing edge.
+///
+/// Node keys can't be changed in place, since that would break the map they are stored in.
+/// [GraphMut::node_weight_mut] therefore panics, remove the node and add it again with the new key instead.
+impl<NodeWeight, EdgeWeight, Direction, S> GraphMut<NodeWeight, EdgeWeight>
+    for GraphMap<NodeWeight, EdgeWeight, Direction, S>
+where
+    NodeWeight: NodeTrait + Debug,
+    Direction: petgraph::EdgeType,
+    S: BuildHasher,
+{
+    fn add_node(&mut self, weight: NodeWeight) -> NodeWeight {
+        GraphMap::add_node(self, weight)
+    }
+
+    fn add_edge(
+        &mut self,
+        from: NodeWeight,
+        to: NodeWeight,
+        weight: EdgeWeight,
+    ) -> (NodeWeight, NodeWeight) {
+        GraphMap::add_edge(self, from, to, weight);
+        graph_map_edge::<_, Direction>(from, to)
+    }
+
+    fn remove_node(&mut self, node: NodeWeight) -> Option<NodeWeight> {
+        GraphMap::remove_node(self, node).then_some(node)
+    }
+
+    fn remove_edge(&mut self, edge: (NodeWeight, NodeWeight)) -> Option<EdgeWeight> {
+        GraphMap::remove_edge(self, edge.0, edge.1)
+    }
+
+    fn node_weight_mut(&mut self, _node: NodeWeight) -> &mut NodeWeight {
+        panic!("Couldn't borrow node weight mutably: GraphMap node weights are keys.")
+    }
+
+    fn edge_weight_mut(&mut self, edge: (NodeWeight, NodeWeight)) -> &mut EdgeWeight {
+        GraphMap::edge_weight_mut(self, edge.0, edge.1)
+            .expect("Couldn't find edge weight: Edge reference invalid.")
+    }
+}
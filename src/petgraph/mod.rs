@@ -1,5 +1,6 @@
 mod file_io;
 mod graph;
+mod graph_mut;
 /// Module with implementations of Pattern Graphs.
 mod pattern_graphs;
 mod print;
@@ -31,12 +31,10 @@ pub fn into_trait_object<N, E, D: EdgeType>(
 /// - Edges:
 /// - FriendOf (since year)
 ///
-
 // Defined Node Types
 ///
 /// Person enum/Uses redundant data for now.
 ///
-
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Person {
     pub name: String,
@@ -258,9 +256,9 @@ pub fn make_sample_graph_undirected<'a>() -> (
     (g, stations, routes)
 }
 
-///
-/// This part defines the movie meta-model for graph queries.
-///
+//
+// This part defines the movie meta-model for graph queries.
+//
 
 ///
 /// Movie type.
@@ -0,0 +1,97 @@
+use graphfind_rs::{
+    adapters::{AsUndirected, Reversed},
+    algorithms::{toposort, Bfs, Direction},
+    graph::Graph as QueryGraph,
+    matcher,
+    mixed_graph::MixedGraph,
+    pattern_matching::{new_pattern, solve_vf, PatternGraph},
+};
+use petgraph::graph::DiGraph;
+
+/// Creates the chain `a -> b -> c` with a self loop at `c`.
+fn make_chain() -> DiGraph<char, u32> {
+    let mut graph = DiGraph::new();
+    let a = graph.add_node('a');
+    let b = graph.add_node('b');
+    let c = graph.add_node('c');
+    graph.add_edge(a, b, 1);
+    graph.add_edge(b, c, 2);
+    graph.add_edge(c, c, 3);
+    graph
+}
+
+/// Reversing a graph swaps the incoming and outgoing edges, as well as the edge endpoints.
+#[test]
+fn test_reversed() {
+    let graph = make_chain();
+    let reversed = Reversed::new(&graph);
+    let nodes: Vec<_> = graph.node_indices().collect();
+    let (a, b, c) = (nodes[0], nodes[1], nodes[2]);
+
+    assert!(reversed.is_directed());
+    assert_eq!(reversed.count_edges(), 3);
+    assert_eq!(reversed.outgoing_edges(a).count(), 0);
+    assert_eq!(reversed.in_degree(a), 1);
+    let e = reversed.incoming_edges(a).next().unwrap();
+    assert_eq!(reversed.adjacent_nodes(e), (b, a));
+    assert_eq!(*reversed.edge_weight(e), 1);
+
+    let order: Vec<_> = Bfs::new(&reversed, c, Direction::Outgoing)
+        .map(|n| *reversed.node_weight(n))
+        .collect();
+    assert_eq!(order, vec!['c', 'b', 'a']);
+
+    // Matches on the reversed graph refer to the nodes of the original graph.
+    let mut pattern = new_pattern();
+    let from = pattern.add_node(matcher!('b'));
+    let to = pattern.add_node(matcher!());
+    pattern.add_edge(from, to, matcher!());
+    let results = solve_vf(&pattern, &reversed);
+    assert_eq!(results.len(), 1);
+    assert_eq!(**results[0].node_weight(to), 'a');
+}
+
+/// An undirected view reports every edge once in each direction.
+#[test]
+fn test_as_undirected() {
+    let graph = make_chain();
+    let undirected = AsUndirected::new(&graph);
+    let nodes: Vec<_> = graph.node_indices().collect();
+
+    assert!(!undirected.is_directed());
+    assert!(undirected.edges().all(|e| !undirected.is_directed_edge(e)));
+    assert_eq!(undirected.degree(nodes[1]), 2);
+    assert_eq!(undirected.incoming_edges(nodes[0]).count(), 1);
+    // The self loop is only reported once.
+    assert_eq!(undirected.outgoing_edges(nodes[2]).count(), 2);
+    assert!(toposort(&undirected).is_err());
+
+    // Directed patterns match undirected edges in both orientations.
+    let mut pattern = new_pattern();
+    let from = pattern.add_node(matcher!('c'));
+    let to = pattern.add_node(matcher!());
+    pattern.add_edge(from, to, matcher!(2));
+    assert!(solve_vf(&pattern, &graph).is_empty());
+    assert_eq!(solve_vf(&pattern, &undirected).len(), 1);
+}
+
+/// Undirected edges of mixed graphs are neither reversed nor reported twice.
+#[test]
+fn test_adapters_on_mixed_graph() {
+    let mut graph = MixedGraph::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    let directed = graph.add_directed_edge(a, b, ());
+    let undirected_edge = graph.add_undirected_edge(a, b, ());
+
+    let reversed = Reversed::new(&graph);
+    assert_eq!(reversed.outgoing_edges(a).count(), 1);
+    assert_eq!(reversed.outgoing_edges(b).count(), 2);
+    assert_eq!(reversed.adjacent_nodes(directed), (b, a));
+    assert_eq!(reversed.adjacent_nodes(undirected_edge), (a, b));
+    assert_eq!(reversed.try_adjacent_nodes(undirected_edge), Some((a, b)));
+
+    let undirected = AsUndirected::new(&graph);
+    assert_eq!(undirected.adjacent_edges(a).count(), 2);
+    assert_eq!(undirected.incoming_edges(b).count(), 2);
+}
@@ -0,0 +1,150 @@
+use std::io::ErrorKind;
+
+use graphfind_rs::{
+    formats::{read_binary, write_binary, write_json, BINARY_MAGIC, BINARY_VERSION},
+    graph::{Graph as RQLGraph, GraphIoError, GraphReadWriter},
+    mixed_graph::MixedGraph,
+    pattern_matching::is_isomorphic,
+};
+use petgraph::{
+    graph::{DiGraph, UnGraph},
+    stable_graph::StableGraph,
+};
+use test_dir::{DirBuilder, TestDir};
+
+pub mod common;
+use common::{make_sample_graph_variant, FriendOf, Person};
+
+/// Check that graphs survive writing and reading, also across backends.
+#[test]
+fn test_binary_round_trip() {
+    let graph = make_sample_graph_variant();
+    let mut buffer = vec![];
+    write_binary(&graph, &mut buffer).unwrap();
+    assert_eq!(buffer[..4], BINARY_MAGIC);
+    assert_eq!(buffer[4..8], [BINARY_VERSION, 1, 4, 4]);
+
+    let read: DiGraph<Person, FriendOf> = read_binary(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+
+    let read: MixedGraph<Person, FriendOf> = read_binary(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+}
+
+/// Check that mixed graphs keep the direction of each edge.
+#[test]
+fn test_binary_mixed_round_trip() {
+    let mut graph = MixedGraph::new();
+    let a = graph.add_node(1u8);
+    let b = graph.add_node(2u8);
+    graph.add_directed_edge(a, b, 'x');
+    graph.add_undirected_edge(b, a, 'y');
+    let mut buffer = vec![];
+    write_binary(&graph, &mut buffer).unwrap();
+
+    let read: MixedGraph<u8, char> = read_binary(buffer.as_slice()).unwrap();
+    let edges: Vec<_> = read
+        .edges()
+        .map(|e| (read.adjacent_nodes(e), read.is_directed_edge(e)))
+        .collect();
+    assert_eq!(edges, vec![((a, b), true), ((b, a), false)]);
+    assert!(is_isomorphic(&graph, &read, u8::eq, char::eq).is_some());
+
+    let mut second_buffer = vec![];
+    write_binary(&read, &mut second_buffer).unwrap();
+    assert_eq!(buffer, second_buffer);
+
+    // Undirected graphs are read as undirected edges.
+    let mut graph = UnGraph::<u8, char>::new_undirected();
+    let a = graph.add_node(1);
+    let b = graph.add_node(2);
+    graph.add_edge(a, b, 'z');
+    let mut buffer = vec![];
+    write_binary(&graph, &mut buffer).unwrap();
+    let read: MixedGraph<u8, char> = read_binary(buffer.as_slice()).unwrap();
+    assert!(read.edges().all(|e| !read.is_directed_edge(e)));
+    assert!(is_isomorphic(&graph, &read, u8::eq, char::eq).is_some());
+}
+
+/// Check the binary format through [GraphReadWriter] on a larger graph,
+/// and that it is more compact than JSON.
+#[test]
+fn test_binary_graph_read_write() {
+    let dir = TestDir::current_rnd();
+    let path = dir.path("graph.bin");
+    let path = path.to_str().unwrap();
+
+    let mut graph = UnGraph::<u32, (u16, bool)>::new_undirected();
+    let nodes: Vec<_> = (0..1000).map(|i| graph.add_node(i)).collect();
+    for i in 0..20000 {
+        graph.add_edge(
+            nodes[i * 7 % 1000],
+            nodes[i * 13 % 1000],
+            (i as u16, i % 3 == 0),
+        );
+    }
+    graph.serialize_graph_to_binary_file(path).unwrap();
+    let read: Box<StableGraph<u32, (u16, bool), petgraph::Undirected>> =
+        GraphReadWriter::deserialize_graph_from_binary_file(path).unwrap();
+    assert_eq!(read.count_nodes(), 1000);
+    assert_eq!(read.count_edges(), 20000);
+    for (e, f) in RQLGraph::edges(&graph).zip(RQLGraph::edges(read.as_ref())) {
+        assert_eq!(graph.adjacent_nodes(e), read.adjacent_nodes(f));
+        assert_eq!(
+            RQLGraph::edge_weight(&graph, e),
+            RQLGraph::edge_weight(read.as_ref(), f)
+        );
+    }
+
+    let (mut binary, mut json) = (vec![], vec![]);
+    graph.write_graph_binary(&mut binary).unwrap();
+    write_json(&graph, &mut json).unwrap();
+    assert!(binary.len() * 4 < json.len());
+}
+
+/// Check that invalid or truncated files are rejected.
+#[test]
+fn test_binary_read_errors() {
+    let mut graph = MixedGraph::new();
+    let a = graph.add_node(1u8);
+    let b = graph.add_node(2u8);
+    graph.add_directed_edge(a, b, 'x');
+    let mut buffer = vec![];
+    write_binary(&graph, &mut buffer).unwrap();
+    let read = |bytes: &[u8]| read_binary::<DiGraph<u8, char>, _, _, _>(bytes);
+    assert!(read(&buffer).is_ok());
+
+    // Wrong magic number and unknown version.
+    for (position, value) in [(0, b'X'), (4, BINARY_VERSION + 1)] {
+        let mut broken = buffer.clone();
+        broken[position] = value;
+        assert!(matches!(
+            read(&broken).unwrap_err(),
+            GraphIoError::Schema { .. }
+        ));
+    }
+    // An edge to a missing node.
+    let mut broken = buffer.clone();
+    broken[11] = 2;
+    assert!(matches!(
+        read(&broken).unwrap_err(),
+        GraphIoError::DanglingEdge { node, .. } if node == "2"
+    ));
+    assert!(matches!(
+        read(&buffer[..buffer.len() - 1]).unwrap_err(),
+        GraphIoError::Io(e) if e.kind() == ErrorKind::UnexpectedEof
+    ));
+    assert!(matches!(
+        read_binary::<UnGraph<u8, char>, _, _, _>(buffer.as_slice()).unwrap_err(),
+        GraphIoError::Schema { .. }
+    ));
+
+    // Undirected edges can't be read into directed graphs.
+    graph.add_undirected_edge(a, b, 'y');
+    let mut buffer = vec![];
+    write_binary(&graph, &mut buffer).unwrap();
+    assert!(matches!(
+        read(&buffer).unwrap_err(),
+        GraphIoError::Schema { .. }
+    ));
+}
@@ -0,0 +1,87 @@
+use std::thread;
+
+use graphfind_rs::{
+    filter_map::FilterMap,
+    filter_pattern,
+    graph::{collect_graph, to_petgraph, CollectedGraph, Graph as RQLGraph},
+    matcher,
+    pattern_matching::{new_pattern, solve_vf, PatternGraph},
+};
+use petgraph::{graph::DiGraph, Directed, Undirected};
+
+pub mod common;
+use common::{make_sample_graph_variant, FriendOf, Person, Role::Student};
+
+/// Check that a copy keeps all nodes, edges and weights, along with the mapping to the original.
+#[test]
+fn test_to_petgraph() {
+    let graph = make_sample_graph_variant();
+    let copy: CollectedGraph<_, _, Person, FriendOf, Directed> = to_petgraph(&graph);
+
+    assert_eq!(copy.graph.node_count(), 4);
+    assert_eq!(copy.graph.edge_count(), 4);
+    for n in graph.nodes() {
+        assert_eq!(copy.graph[copy.node_indices[&n]], graph[n]);
+    }
+    for e in RQLGraph::edges(&graph) {
+        let (a, b) = RQLGraph::adjacent_nodes(&graph, e);
+        let copied = copy.edge_indices[&e];
+        assert_eq!(copy.graph[copied], graph[e]);
+        assert_eq!(
+            copy.graph.edge_endpoints(copied),
+            Some((copy.node_indices[&a], copy.node_indices[&b]))
+        );
+    }
+}
+
+/// Check that filtered views can be copied and outlive their base graph.
+#[test]
+fn test_collect_filter_map() {
+    let copy: CollectedGraph<_, _, String, i32, Undirected> = {
+        let graph = make_sample_graph_variant();
+        let students = filter_pattern!(&graph, node_pattern: Person { role: Student { .. }, .. });
+        collect_graph(&students, |p| p.name.clone(), |f| f.since_year)
+    };
+
+    let mut names: Vec<_> = copy.graph.node_weights().cloned().collect();
+    names.sort();
+    assert_eq!(names, vec!["horst", "stefan", "tobias"]);
+    // Only the friendship between tobias and horst connects two students.
+    assert_eq!(copy.graph.edge_weights().collect::<Vec<_>>(), vec![&2020]);
+    assert!(!copy.graph.is_directed());
+    assert_eq!(copy.edge_indices.len(), 1);
+}
+
+/// Check that match results can be copied and sent to another thread.
+#[test]
+fn test_collect_matched_graph() {
+    let mut graph: DiGraph<u32, &str> = DiGraph::new();
+    let a = graph.add_node(1);
+    let b = graph.add_node(2);
+    let c = graph.add_node(3);
+    graph.add_edge(a, b, "x");
+    graph.add_edge(b, c, "y");
+
+    let mut pattern = new_pattern();
+    let first = pattern.add_node(matcher!(1));
+    let second = pattern.add_node(matcher!());
+    pattern.add_edge(first, second, matcher!());
+    let results = solve_vf(&pattern, &graph);
+    assert_eq!(results.len(), 1);
+
+    let copy: CollectedGraph<_, _, u32, String, Directed> =
+        collect_graph(&results[0], |n| **n, |e| e.to_string());
+    let (first, second) = (copy.node_indices[&first], copy.node_indices[&second]);
+    let owned = copy.graph;
+    let weights = thread::spawn(move || {
+        (
+            owned[first],
+            owned[second],
+            owned.edge_weights().next().cloned(),
+        )
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(weights, (1, 2, Some("x".to_string())));
+}
@@ -0,0 +1,105 @@
+use graphfind_rs::{
+    algorithms::{
+        condensation, strongly_connected_components, toposort, weakly_connected_components,
+    },
+    graph::Graph as QueryGraph,
+    matcher,
+    pattern_matching::{new_pattern, solve_vf, PatternGraph},
+};
+use petgraph::graph::{DiGraph, NodeIndex, UnGraph};
+
+/// Creates a directed graph with two cycles `a -> b -> c -> a` and `d <-> e`,
+/// connected by the edge `c -> d`, as well as the isolated node `f`.
+fn make_cyclic_graph() -> (DiGraph<char, u32>, Vec<NodeIndex>) {
+    let mut graph = DiGraph::new();
+    let nodes: Vec<_> = ('a'..='f').map(|c| graph.add_node(c)).collect();
+    for (i, (a, b)) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3)]
+        .into_iter()
+        .enumerate()
+    {
+        graph.add_edge(nodes[a], nodes[b], i as u32);
+    }
+    (graph, nodes)
+}
+
+/// Returns the sorted node weights of a graph.
+fn sorted_weights<G: QueryGraph<N, E>, N: Ord + Clone, E>(graph: &G) -> Vec<N> {
+    let mut weights: Vec<_> = graph.node_weights().cloned().collect();
+    weights.sort();
+    weights
+}
+
+/// Check the weakly connected components of directed and undirected graphs.
+#[test]
+fn test_weakly_connected_components() {
+    let (graph, _) = make_cyclic_graph();
+    let mut components: Vec<_> = weakly_connected_components(&graph)
+        .iter()
+        .map(|c| (sorted_weights(c), c.count_edges()))
+        .collect();
+    components.sort();
+    assert_eq!(
+        components,
+        vec![(vec![&'a', &'b', &'c', &'d', &'e'], 6), (vec![&'f'], 0)]
+    );
+
+    let mut undirected = UnGraph::<u32, ()>::new_undirected();
+    let nodes: Vec<_> = (0..4).map(|i| undirected.add_node(i)).collect();
+    undirected.add_edge(nodes[0], nodes[1], ());
+    undirected.add_edge(nodes[2], nodes[3], ());
+    undirected.add_edge(nodes[3], nodes[3], ());
+    let components = weakly_connected_components(&undirected);
+    assert_eq!(components.len(), 2);
+    assert_eq!(components.iter().map(|c| c.count_edges()).sum::<usize>(), 3);
+    assert_eq!(strongly_connected_components(&undirected).len(), 2);
+}
+
+/// Check that strongly connected components are found in reverse topological order,
+/// and that their views can be searched for patterns.
+#[test]
+fn test_strongly_connected_components() {
+    let (graph, _) = make_cyclic_graph();
+    let components = strongly_connected_components(&graph);
+    let weights: Vec<_> = components.iter().map(sorted_weights).collect();
+    assert_eq!(
+        weights,
+        vec![vec![&'d', &'e'], vec![&'a', &'b', &'c'], vec![&'f']]
+    );
+    // The edge c -> d doesn't belong to any component.
+    assert_eq!(
+        components
+            .iter()
+            .map(|c| c.count_edges())
+            .collect::<Vec<_>>(),
+        vec![2, 3, 0]
+    );
+
+    // Each component of size two or more contains a single cycle through one of its nodes.
+    let mut pattern = new_pattern();
+    let a = pattern.add_node(matcher!());
+    let b = pattern.add_node(matcher!());
+    pattern.add_edge(a, b, matcher!());
+    assert_eq!(solve_vf(&pattern, &components[0]).len(), 2);
+    assert_eq!(solve_vf(&pattern, &components[1]).len(), 3);
+    assert!(solve_vf(&pattern, &components[2]).is_empty());
+}
+
+/// The condensation of a graph is acyclic, and keeps the edges between components.
+#[test]
+fn test_condensation() {
+    let (graph, nodes) = make_cyclic_graph();
+    let condensed = condensation(&graph);
+    assert_eq!(condensed.node_count(), 3);
+    assert_eq!(condensed.edge_count(), 1);
+
+    let order = toposort(&condensed).unwrap();
+    assert_eq!(order.len(), 3);
+    let edge = condensed.edge_indices().next().unwrap();
+    let (from, to) = condensed.edge_endpoints(edge).unwrap();
+    assert!(condensed[from].contains(&nodes[2]));
+    assert!(condensed[to].contains(&nodes[3]));
+    assert_eq!(
+        condensed[edge],
+        vec![graph.find_edge(nodes[2], nodes[3]).unwrap()]
+    );
+}
@@ -0,0 +1,127 @@
+use graphfind_rs::{
+    csr_graph::{CsrEdgeIndex, CsrGraph, CsrNodeIndex},
+    graph::Graph as QueryGraph,
+    matcher,
+    pattern_matching::{new_pattern, solve_vf, PatternGraph},
+};
+
+pub mod common;
+use common::{make_sample_graph, make_sample_graph_undirected, FriendOf, Person};
+
+/// Copy the sample graph into a CSR graph, and check that the structure is preserved.
+#[test]
+fn test_csr_from_directed_graph() {
+    let (base, _, edge_data) = make_sample_graph();
+    let csr = CsrGraph::from_graph(&base);
+
+    assert!(csr.is_directed());
+    assert_eq!(csr.count_nodes(), 4);
+    assert_eq!(csr.count_edges(), 5);
+
+    // Nodes keep the order of the source graph.
+    for (n, csr_n) in base.node_indices().zip(csr.nodes()) {
+        assert_eq!(csr_n.index(), n.index());
+        assert_eq!(csr.node_weight(csr_n), &base[n]);
+    }
+
+    // Every edge of the base graph is found by its endpoints.
+    for (a, b, weight) in edge_data.values() {
+        let (a, b) = (CsrNodeIndex::new(a.index()), CsrNodeIndex::new(b.index()));
+        let e = csr.find_edge(a, b).unwrap();
+        assert_eq!(csr.adjacent_nodes(e), (a, b));
+        assert_eq!(csr.edge_weight(e), weight);
+    }
+    assert!(!csr.contains_edge_between(CsrNodeIndex::new(2), CsrNodeIndex::new(0)));
+
+    // Degrees agree with the edge iterators.
+    for n in csr.nodes() {
+        assert_eq!(csr.out_degree(n), csr.outgoing_edges(n).count());
+        assert_eq!(csr.in_degree(n), csr.incoming_edges(n).count());
+        assert_eq!(csr.degree(n), csr.adjacent_edges(n).count());
+        for e in csr.outgoing_edges(n) {
+            assert_eq!(csr.adjacent_nodes(e).0, n);
+        }
+        for e in csr.incoming_edges(n) {
+            assert_eq!(csr.adjacent_nodes(e).1, n);
+        }
+    }
+}
+
+/// Outgoing edges are sorted by their targets.
+#[test]
+fn test_csr_sorted_adjacency() {
+    let mut base = petgraph::Graph::<(), u32>::new();
+    let nodes: Vec<_> = (0..5).map(|_| base.add_node(())).collect();
+    for (i, target) in [4, 1, 3, 1, 2].into_iter().enumerate() {
+        base.add_edge(nodes[0], nodes[target], i as u32);
+    }
+    let csr = CsrGraph::from_graph(&base);
+
+    let targets: Vec<_> = csr
+        .outgoing_edges(CsrNodeIndex::new(0))
+        .map(|e| csr.adjacent_nodes(e).1.index())
+        .collect();
+    assert_eq!(targets, vec![1, 1, 2, 3, 4]);
+    // Parallel edges keep their order.
+    assert_eq!(
+        *csr.edge_weight(
+            csr.find_edge(CsrNodeIndex::new(0), CsrNodeIndex::new(1))
+                .unwrap()
+        ),
+        1
+    );
+    assert_eq!(*csr.edge_weight(CsrEdgeIndex::new(1)), 3);
+}
+
+/// Undirected graphs report all edges as incoming and outgoing edges.
+#[test]
+fn test_csr_undirected() {
+    let (base, stations, routes) = make_sample_graph_undirected();
+    let csr = CsrGraph::from_graph(&base);
+
+    assert!(!csr.is_directed());
+    for station in stations.keys() {
+        let n = CsrNodeIndex::new(station.index());
+        let expected = routes
+            .values()
+            .filter(|(a, b, _)| a == station || b == station)
+            .count();
+        assert_eq!(csr.incoming_edges(n).count(), expected);
+        assert_eq!(csr.outgoing_edges(n).count(), expected);
+        assert_eq!(csr.adjacent_edges(n).count(), expected);
+        assert_eq!(csr.out_degree(n), expected);
+        assert_eq!(csr.in_degree(n), expected);
+        assert_eq!(csr.degree(n), expected);
+    }
+    for (a, b, _) in routes.values() {
+        let (a, b) = (CsrNodeIndex::new(a.index()), CsrNodeIndex::new(b.index()));
+        assert!(csr.contains_edge_between(a, b));
+        assert!(csr.contains_edge_between(b, a));
+    }
+}
+
+/// Pattern matching finds the same results on the CSR copy as on the original graph.
+#[test]
+fn test_csr_pattern_match() {
+    let base = make_sample_graph().0;
+    let csr = CsrGraph::from_graph(&base);
+
+    let mut pattern = new_pattern();
+    let a = pattern.add_node(matcher!(Person { .. }));
+    let b = pattern.add_node(matcher!(Person { .. }));
+    pattern.add_edge(a, b, matcher!(FriendOf { since_year: 2010 }));
+    pattern.add_edge(b, a, matcher!());
+
+    let base_results = solve_vf(&pattern, &base);
+    let csr_results = solve_vf(&pattern, &csr);
+    assert_eq!(base_results.len(), 2);
+    assert_eq!(csr_results.len(), base_results.len());
+}
+
+/// Invalid references are rejected.
+#[test]
+#[should_panic(expected = "Couldn't find node weight: Node reference invalid.")]
+fn test_csr_invalid_node() {
+    let csr = CsrGraph::from_graph(&make_sample_graph().0);
+    csr.node_weight(CsrNodeIndex::new(4));
+}
@@ -0,0 +1,184 @@
+use graphfind_rs::{
+    formats::{read_csv, write_csv, CsvOptions},
+    graph::{Graph as RQLGraph, GraphIoError},
+    mixed_graph::MixedGraph,
+    pattern_matching::is_isomorphic,
+};
+use petgraph::{
+    graph::{DiGraph, UnGraph},
+    stable_graph::StableGraph,
+};
+use serde::{Deserialize, Serialize};
+
+pub mod common;
+use common::{make_sample_graph_undirected, make_sample_graph_variant, FriendOf, Person};
+
+/// Writes `g` with the given options, and returns the node and edge lists.
+fn write<G, NW, EW>(g: &G, options: &CsvOptions) -> (String, String)
+where
+    G: RQLGraph<NW, EW>,
+    NW: Serialize,
+    EW: Serialize,
+{
+    let (mut nodes, mut edges) = (vec![], vec![]);
+    write_csv(g, &mut nodes, &mut edges, options).unwrap();
+    (
+        String::from_utf8(nodes).unwrap(),
+        String::from_utf8(edges).unwrap(),
+    )
+}
+
+/// Check that graphs with struct and enum weights survive writing and reading.
+#[test]
+fn test_csv_round_trip() {
+    let graph = make_sample_graph_variant();
+    let options = CsvOptions::default();
+    let (nodes, edges) = write(&graph, &options);
+    assert!(nodes.starts_with("id,"));
+    assert!(nodes.contains(r#"0,99,tobias,"{""Student"":{""matrical_number"":900000}}""#));
+    assert!(edges.starts_with("source,target,since_year\n0,2,2020\n"));
+
+    let read: DiGraph<Person, FriendOf> =
+        read_csv(nodes.as_bytes(), edges.as_bytes(), &options).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+
+    let read: StableGraph<Person, FriendOf> =
+        read_csv(nodes.as_bytes(), edges.as_bytes(), &options).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+
+    // Plain weights are written to a single column.
+    let (graph, _, _) = make_sample_graph_undirected();
+    let (nodes, edges) = write(&graph, &options);
+    assert!(nodes.starts_with("id,weight\n0,"), "{nodes}");
+    let read: UnGraph<String, i32> =
+        read_csv(nodes.as_bytes(), edges.as_bytes(), &options).unwrap();
+    assert!(is_isomorphic(&graph, &read, |a, b| a == b, i32::eq).is_some());
+}
+
+/// Check that the directions of mixed graph edges are written to a direction column and read back.
+#[test]
+fn test_csv_mixed_round_trip() {
+    let mut graph = MixedGraph::new();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    graph.add_directed_edge(a, b, 1);
+    graph.add_undirected_edge(b, a, 2);
+    let options = CsvOptions::default();
+    let (nodes, edges) = write(&graph, &options);
+    assert_eq!(
+        edges,
+        "source,target,directed,weight\n0,1,true,1\n1,0,false,2\n"
+    );
+
+    let read: MixedGraph<String, u32> =
+        read_csv(nodes.as_bytes(), edges.as_bytes(), &options).unwrap();
+    let directions: Vec<_> = read.edges().map(|e| read.is_directed_edge(e)).collect();
+    assert_eq!(directions, vec![true, false]);
+    assert_eq!(write(&read, &options), (nodes.clone(), edges.clone()));
+
+    // Undirected edges can't be read into directed graphs.
+    let err =
+        read_csv::<DiGraph<String, u32>, _, _, _, _>(nodes.as_bytes(), edges.as_bytes(), &options)
+            .unwrap_err();
+    assert!(
+        matches!(err, GraphIoError::Schema { line: Some(3), .. }),
+        "{err}"
+    );
+}
+
+/// A station, as stored by other tools.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Station {
+    code: String,
+    name: String,
+    zone: u8,
+    accessible: Option<bool>,
+}
+
+/// Check reading lists with custom columns, and writing node IDs taken from the weights.
+#[test]
+fn test_csv_custom_columns() {
+    let options = CsvOptions {
+        id_column: "code".to_string(),
+        source_column: "from".to_string(),
+        target_column: "to".to_string(),
+        directed_column: "directed".to_string(),
+        delimiter: b';',
+    };
+    let nodes = "code;name;zone;accessible\nET;Ehinger Tor;1;true\nTH;Theater;2;\n";
+    let edges = "from;to;line\nET;TH;2\nTH;ET;2\n";
+    let graph: DiGraph<Station, String> =
+        read_csv(nodes.as_bytes(), edges.as_bytes(), &options).unwrap();
+
+    let stations: Vec<_> = graph.node_weights().collect();
+    assert_eq!(
+        stations,
+        vec![
+            &Station {
+                code: "ET".to_string(),
+                name: "Ehinger Tor".to_string(),
+                zone: 1,
+                accessible: Some(true)
+            },
+            &Station {
+                code: "TH".to_string(),
+                name: "Theater".to_string(),
+                zone: 2,
+                accessible: None
+            }
+        ]
+    );
+    assert_eq!(graph.edge_weights().collect::<Vec<_>>(), vec!["2", "2"]);
+
+    // The codes of the stations are used as node IDs.
+    let (written_nodes, written_edges) = write(&graph, &options);
+    assert_eq!(
+        written_nodes,
+        "accessible;code;name;zone\ntrue;ET;Ehinger Tor;1\n;TH;Theater;2\n"
+    );
+    assert_eq!(written_edges, "from;to;weight\nET;TH;2\nTH;ET;2\n");
+}
+
+/// Check that invalid lists are rejected, with the line of the problem.
+#[test]
+fn test_csv_read_errors() {
+    let read = |nodes: &str, edges: &str| {
+        read_csv::<DiGraph<String, ()>, _, _, _, _>(
+            nodes.as_bytes(),
+            edges.as_bytes(),
+            &CsvOptions::default(),
+        )
+    };
+    let nodes = "id,name\na,A\nb,B\n";
+    assert!(read(nodes, "source,target\na,b\n").is_ok());
+
+    let err = read(nodes, "source,target\na,b\nb,c\n").unwrap_err();
+    assert!(
+        matches!(&err, GraphIoError::DanglingEdge { line: Some(3), node } if node == "c"),
+        "{err}"
+    );
+    assert!(err.to_string().starts_with("Line 3:"), "{err}");
+
+    for (nodes, edges, line) in [
+        ("id,name\na,A\na,B\n", "source,target\n", Some(3)),
+        ("name\nA\n", "source,target\n", None),
+        (nodes, "source,destination\na,b\n", None),
+        ("id,name,age\na,A,1\n", "source,target\n", Some(2)),
+    ] {
+        let err = read(nodes, edges).unwrap_err();
+        assert!(matches!(err, GraphIoError::Schema { .. }), "{err}");
+        assert_eq!(err.line(), line, "{err}");
+    }
+    let err = read(nodes, "source,target\na,b,c\n").unwrap_err();
+    assert!(
+        matches!(
+            err,
+            GraphIoError::Syntax {
+                line: 2,
+                column: None,
+                ..
+            }
+        ),
+        "{err}"
+    );
+}
@@ -0,0 +1,165 @@
+use graphfind_rs::{
+    formats::{read_dot, DotAttributes},
+    graph::{Graph as RQLGraph, GraphIoError, VizDotGraph},
+    mixed_graph::MixedGraph,
+    pattern_matching::is_isomorphic,
+};
+use petgraph::graph::{DiGraph, UnGraph};
+use serde::Deserialize;
+
+pub mod common;
+use common::{make_sample_graph_undirected, make_sample_graph_variant};
+
+/// Check that reading printed graphs gives the same graph, with the printed weights as labels.
+#[test]
+fn test_dot_print_round_trip() {
+    let graph = make_sample_graph_variant();
+    let read: DiGraph<DotAttributes, DotAttributes> = read_dot(graph.print().as_bytes()).unwrap();
+    assert!(is_isomorphic(
+        &graph,
+        &read,
+        |p, attributes| attributes["label"] == format!("{p:?}"),
+        |f, attributes| attributes["label"] == format!("{f:?}"),
+    )
+    .is_some());
+
+    // Labels can also be deserialized into the weights directly.
+    let (graph, _, _) = make_sample_graph_undirected();
+    let read: UnGraph<String, i32> = read_dot(graph.print().as_bytes()).unwrap();
+    assert!(is_isomorphic(&graph, &read, |a, b| format!("{a:?}") == *b, i32::eq).is_some());
+}
+
+/// Check default attributes, subgraphs, edge chains and comments.
+#[test]
+fn test_dot_read_fixture() {
+    let text = r#"
+        /* A hand-written fixture. */
+        digraph Fixture {
+            rankdir = LR;
+            node [shape = box, color = "red"]
+            a [label = "Start" + " node"]
+            a -> b -> c [weight = 2]   // Two edges.
+            # Preprocessor-style comments are skipped.
+            subgraph cluster_0 {
+                node [color = blue];
+                edge [style = dashed];
+                d; e:port:n
+                d -> e
+            }
+            c -> { d e } [label = <<b>bold</b>>]
+            "b" -> a
+        }
+    "#;
+    let graph: DiGraph<DotAttributes, DotAttributes> = read_dot(text.as_bytes()).unwrap();
+    assert_eq!(graph.count_nodes(), 5);
+    assert_eq!(graph.count_edges(), 6);
+
+    let node = |id: &str| {
+        graph
+            .nodes()
+            .find(|n| RQLGraph::node_weight(&graph, *n)["label"] == id)
+            .unwrap()
+    };
+    let (a, b, c, d, e) = (
+        node("Start node"),
+        node("b"),
+        node("c"),
+        node("d"),
+        node("e"),
+    );
+    assert_eq!(RQLGraph::node_weight(&graph, a)["shape"], "box");
+    assert_eq!(RQLGraph::node_weight(&graph, b)["color"], "red");
+    assert_eq!(RQLGraph::node_weight(&graph, d)["color"], "blue");
+    assert_eq!(RQLGraph::node_weight(&graph, e)["color"], "blue");
+
+    let edge = |x, y| graph.find_edge(x, y).unwrap();
+    assert_eq!(RQLGraph::edge_weight(&graph, edge(a, b))["weight"], "2");
+    assert_eq!(RQLGraph::edge_weight(&graph, edge(b, c))["weight"], "2");
+    assert_eq!(RQLGraph::edge_weight(&graph, edge(d, e))["style"], "dashed");
+    assert!(!RQLGraph::edge_weight(&graph, edge(c, d)).contains_key("style"));
+    assert_eq!(
+        RQLGraph::edge_weight(&graph, edge(c, e))["label"],
+        "<b>bold</b>"
+    );
+    assert!(RQLGraph::edge_weight(&graph, edge(b, a)).is_empty());
+}
+
+/// A road between towns, with attributes parsed into numbers.
+#[derive(Deserialize, Debug, PartialEq)]
+struct Road {
+    length: f64,
+    lanes: Option<u8>,
+}
+
+/// Check that weights are deserialized from attributes, and that strict graphs merge edges.
+#[test]
+fn test_dot_read_serde_weights() {
+    let text = r#"
+        strict graph {
+            a -- b [length = 2.5, lanes = 2]
+            b -- a [length = 3]
+            b -- c [length = "7"]
+        }
+    "#;
+    let graph: UnGraph<String, Road> = read_dot(text.as_bytes()).unwrap();
+    assert_eq!(graph.count_edges(), 2);
+    let road = |x: u32, y: u32| {
+        RQLGraph::edge_weight(&graph, graph.find_edge(x.into(), y.into()).unwrap())
+    };
+    assert_eq!(
+        road(0, 1),
+        &Road {
+            length: 3.0,
+            lanes: Some(2)
+        }
+    );
+    assert_eq!(
+        road(1, 2),
+        &Road {
+            length: 7.0,
+            lanes: None
+        }
+    );
+    assert_eq!(RQLGraph::node_weight(&graph, 2.into()), "c");
+
+    // Mixed graphs hold the edges of undirected graphs as undirected edges.
+    let mixed: MixedGraph<String, Road> = read_dot(text.as_bytes()).unwrap();
+    assert_eq!(mixed.count_edges(), 2);
+    assert!(mixed.edges().all(|e| !mixed.is_directed_edge(e)));
+}
+
+/// Check that malformed or unsupported input is rejected.
+#[test]
+fn test_dot_read_errors() {
+    let read = |text: &str| read_dot::<DiGraph<DotAttributes, ()>, _, _, _>(text.as_bytes());
+    for text in [
+        "digraph { a -> }",
+        "digraph { a -- b }",
+        "digraph { a [label = \"unterminated] }",
+        "digraph { a -> b",
+        "digraph { a -> b [label] }",
+        "graph { a -- b }",
+        "tree { a }",
+    ] {
+        let err = read(text).unwrap_err();
+        assert!(!matches!(err, GraphIoError::Io(_)), "{text}: {err}");
+    }
+
+    let err = read("digraph {\n  a -> b;\n  b -> ; }").unwrap_err();
+    assert!(
+        matches!(
+            err,
+            GraphIoError::Syntax {
+                line: 3,
+                column: Some(8),
+                ..
+            }
+        ),
+        "{err}"
+    );
+    assert!(matches!(
+        read("graph { a -- b }").unwrap_err(),
+        GraphIoError::Schema { line: None, .. }
+    ));
+    assert!(read("digraph { a -> b }").is_ok());
+}
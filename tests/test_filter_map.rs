@@ -1,6 +1,12 @@
 use std::vec;
 
-use graphfind_rs::{filter_pattern, graph::Graph as RQLGraph};
+use graphfind_rs::{
+    algorithms::Direction,
+    filter_pattern,
+    graph::Graph as RQLGraph,
+    matcher,
+    pattern_matching::{new_pattern, solve_vf_with_refs, PatternGraph},
+};
 use petgraph::{
     graph::{EdgeIndex, NodeIndex},
     stable_graph::DefaultIx,
@@ -14,8 +20,8 @@ use common::{
 };
 use graphfind_rs::filter_map::FilterMap;
 
-/// Function Tests for filter_map
-///
+// Function Tests for filter_map
+//
 
 ///
 /// Returns a petgraph consisting of 3000 nodes (weight 0, ..., 2999)
@@ -330,6 +336,85 @@ fn test_wrong_edge_ends() {
     let (_s, _e) = result.adjacent_nodes(EdgeIndex::from(0));
 }
 
+/// Test that fallible lookups of filtered out elements return None.
+#[test]
+fn test_try_lookups() {
+    let graph = into_trait_object(make_sample_graph_mass_filter_map());
+    let result = FilterMap::weight_filter(&graph, |n| n % 3 != 0, |_| true);
+
+    assert!(graph.contains_node(NodeIndex::from(0)));
+    assert!(!result.contains_node(NodeIndex::from(0)));
+    assert_eq!(result.try_node_weight(NodeIndex::from(0)), None);
+    assert_eq!(result.try_node_weight(NodeIndex::from(1)), Some(&&1));
+
+    assert!(!result.contains_edge(EdgeIndex::from(0)));
+    assert_eq!(result.try_edge_weight(EdgeIndex::from(0)), None);
+    assert_eq!(result.try_adjacent_nodes(EdgeIndex::from(0)), None);
+    assert_eq!(result.try_edge_weight(EdgeIndex::from(2000)), None);
+}
+
+/// Test that induced subgraphs keep exactly the edges between the given nodes.
+#[test]
+fn test_induced_subgraph() {
+    let graph = make_sample_graph_mass_filter_map();
+    // Nodes 0, 1, 2 form a star around 0, node 3 belongs to the next star.
+    let nodes = [0, 1, 3].map(NodeIndex::from);
+    let result = FilterMap::induced_subgraph(&graph, nodes);
+
+    assert_eq!(result.count_nodes(), 3);
+    assert_eq!(result.count_edges(), 1);
+    assert_eq!(**result.edge_weight(EdgeIndex::from(0)), 0);
+    assert_eq!(result.adjacent_edges(NodeIndex::from(3)).count(), 0);
+}
+
+/// Test neighborhoods with different radii and directions.
+#[test]
+fn test_k_hop_neighborhood() {
+    let mut graph: Graph<u32, ()> = Graph::new();
+    let chain: Vec<_> = (0..6).map(|i| graph.add_node(i)).collect();
+    for pair in chain.windows(2) {
+        graph.add_edge(pair[0], pair[1], ());
+    }
+
+    let weights = |g: &FilterMap<_, _, &u32, _, _>| {
+        let mut weights: Vec<_> = g.node_weights().map(|w| **w).collect();
+        weights.sort();
+        weights
+    };
+
+    let around_two = FilterMap::k_hop_neighborhood(&graph, [chain[2]], 1, Direction::Both);
+    assert_eq!(weights(&around_two), vec![1, 2, 3]);
+    assert_eq!(around_two.count_edges(), 2);
+
+    let downstream = FilterMap::k_hop_neighborhood(&graph, [chain[2]], 2, Direction::Outgoing);
+    assert_eq!(weights(&downstream), vec![2, 3, 4]);
+
+    let around_ends =
+        FilterMap::k_hop_neighborhood(&graph, [chain[0], chain[5]], 1, Direction::Both);
+    assert_eq!(weights(&around_ends), vec![0, 1, 4, 5]);
+    assert_eq!(around_ends.count_edges(), 2);
+}
+
+/// Test that the context of a pattern match contains the matched nodes and their surroundings.
+#[test]
+fn test_match_context() {
+    let graph = make_sample_graph_mass_filter_map();
+    let mut pattern = new_pattern();
+    let center = pattern.add_node(matcher!(900));
+    let leaf = pattern.add_node(matcher!(i if i % 3 == 2));
+    pattern.add_edge(center, leaf, matcher!());
+    let results = solve_vf_with_refs(&pattern, &graph);
+    assert_eq!(results.len(), 1);
+
+    let (_, refs) = &results[0];
+    assert_eq!(graph[refs.nodes[&center]], 900);
+    let context = FilterMap::match_context(&graph, &refs.nodes, 1, Direction::Both);
+    let mut weights: Vec<_> = context.node_weights().map(|w| **w).collect();
+    weights.sort();
+    assert_eq!(weights, vec![900, 901, 902]);
+    assert_eq!(context.count_edges(), 2);
+}
+
 /// Test that the filter_pattern macro evaluates to correct code.
 #[test]
 fn test_filter_pattern() {
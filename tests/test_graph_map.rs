@@ -0,0 +1,133 @@
+use graphfind_rs::{
+    filter_map::FilterMap,
+    graph::Graph as QueryGraph,
+    matcher,
+    pattern_matching::{new_pattern, solve_vf, PatternGraph},
+};
+use petgraph::graphmap::{DiGraphMap, UnGraphMap};
+
+/// Creates a small dependency graph keyed by crate names.
+fn make_sample_graph_map() -> DiGraphMap<&'static str, u32> {
+    let mut graph = DiGraphMap::new();
+    graph.add_edge("app", "graphfind", 1);
+    graph.add_edge("graphfind", "petgraph", 6);
+    graph.add_edge("graphfind", "serde", 1);
+    graph.add_edge("petgraph", "serde", 1);
+    graph.add_edge("app", "serde", 1);
+    graph
+}
+
+/// Check that node keys act as both references and weights,
+/// and that edges are referenced by their endpoints.
+#[test]
+fn test_graph_map_structure() {
+    let graph = make_sample_graph_map();
+
+    assert!(QueryGraph::is_directed(&graph));
+    assert_eq!(graph.count_nodes(), 4);
+    assert_eq!(graph.count_edges(), 5);
+    assert_eq!(*QueryGraph::node_weight(&graph, "serde"), "serde");
+    assert_eq!(
+        *QueryGraph::edge_weight(&graph, ("graphfind", "petgraph")),
+        6
+    );
+
+    let mut incoming: Vec<_> = graph.incoming_edges("serde").collect();
+    incoming.sort();
+    assert_eq!(
+        incoming,
+        vec![
+            ("app", "serde"),
+            ("graphfind", "serde"),
+            ("petgraph", "serde")
+        ]
+    );
+    assert_eq!(graph.outgoing_edges("app").count(), 2);
+    assert_eq!(graph.adjacent_edges("graphfind").count(), 3);
+
+    for edge in QueryGraph::edges(&graph) {
+        assert_eq!(graph.adjacent_nodes(edge), edge);
+        assert!(graph.is_directed_edge(edge));
+    }
+
+    let mut weights: Vec<_> = QueryGraph::node_weights(&graph).copied().collect();
+    weights.sort();
+    assert_eq!(weights, vec!["app", "graphfind", "petgraph", "serde"]);
+}
+
+/// Edge references of undirected graph maps don't depend on the
+/// direction from which the edge was found.
+#[test]
+fn test_graph_map_undirected_edge_refs() {
+    let mut graph: UnGraphMap<u64, &str> = UnGraphMap::new();
+    graph.add_edge(3u64, 1u64, "a");
+    graph.add_edge(1u64, 2u64, "b");
+
+    assert_eq!(graph.incoming_edges(1).count(), 2);
+    let mut from_three: Vec<_> = graph.outgoing_edges(3).collect();
+    let mut from_one: Vec<_> = graph.incoming_edges(1).collect();
+    from_three.sort();
+    from_one.sort();
+    assert_eq!(from_three, vec![(1, 3)]);
+    assert_eq!(from_one, vec![(1, 2), (1, 3)]);
+    assert_eq!(*QueryGraph::edge_weight(&graph, (1, 3)), "a");
+}
+
+/// Invalid node keys are rejected.
+#[test]
+#[should_panic(expected = "Couldn't find node weight: Node reference invalid.")]
+fn test_graph_map_invalid_node() {
+    let graph = make_sample_graph_map();
+    QueryGraph::node_weight(&graph, "bimap");
+}
+
+/// Find all paths of length two in the dependency graph.
+#[test]
+fn test_graph_map_pattern_match() {
+    let graph = make_sample_graph_map();
+
+    let mut pattern = new_pattern();
+    let a = pattern.add_node(matcher!());
+    let b = pattern.add_node(matcher!());
+    let c = pattern.add_node(matcher!(&"serde"));
+    pattern.add_edge(a, b, matcher!());
+    pattern.add_edge(b, c, matcher!());
+
+    let results = solve_vf(&pattern, &graph);
+    let mut paths: Vec<_> = results
+        .iter()
+        .map(|r| (**r.node_weight(a), **r.node_weight(b)))
+        .collect();
+    paths.sort();
+    assert_eq!(paths, vec![("app", "graphfind"), ("graphfind", "petgraph")]);
+}
+
+/// Filter a graph map by its edge weights.
+#[test]
+fn test_graph_map_weight_filter() {
+    let graph = make_sample_graph_map();
+    let filtered = FilterMap::weight_filter(&graph, |_| true, |w| *w == 1);
+
+    assert_eq!(filtered.count_nodes(), 4);
+    assert_eq!(filtered.count_edges(), 4);
+    assert!(!filtered.edges().any(|e| e == ("graphfind", "petgraph")));
+}
+
+/// Check that fallible lookups use the node keys and endpoint pairs.
+#[test]
+fn test_graph_map_try_lookups() {
+    let graph = make_sample_graph_map();
+
+    assert_eq!(QueryGraph::try_node_weight(&graph, "serde"), Some(&"serde"));
+    assert_eq!(QueryGraph::try_node_weight(&graph, "tokio"), None);
+    assert!(QueryGraph::contains_edge(&graph, ("app", "serde")));
+    assert!(!QueryGraph::contains_edge(&graph, ("serde", "app")));
+    assert_eq!(
+        QueryGraph::try_edge_weight(&graph, ("graphfind", "petgraph")),
+        Some(&6)
+    );
+    assert_eq!(
+        QueryGraph::try_adjacent_nodes(&graph, ("serde", "app")),
+        None
+    );
+}
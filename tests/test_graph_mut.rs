@@ -0,0 +1,139 @@
+use graphfind_rs::graph::{Graph, GraphMut};
+use petgraph::{
+    graphmap::{DiGraphMap, UnGraphMap},
+    stable_graph::StableGraph,
+};
+
+pub mod common;
+use common::{new_professor, new_student, FriendOf, Person};
+
+/// Builds a small friendship graph through the GraphMut trait only,
+/// returning the references of the added nodes.
+fn build_friends<G>(graph: &mut G) -> Vec<G::NodeRef>
+where
+    G: GraphMut<Person, FriendOf>,
+{
+    let t = graph.add_node(new_student("tobias", 99, 900000));
+    let s = graph.add_node(new_student("stefan", 9, 89000));
+    let b = graph.add_node(new_professor("bettina", 36, "Faculty of Mathematics"));
+    graph.add_edge(t, b, FriendOf::new(2020));
+    graph.add_edge(s, b, FriendOf::new(2018));
+    graph.add_edge(b, s, FriendOf::new(2018));
+    vec![t, s, b]
+}
+
+/// Modifies weights through the GraphMut trait: everybody gets a year older,
+/// and friendships are dated back by one year.
+fn age_everybody<G>(graph: &mut G)
+where
+    G: GraphMut<Person, FriendOf>,
+{
+    let nodes: Vec<_> = graph.nodes().collect();
+    for n in nodes {
+        graph.node_weight_mut(n).age += 1;
+    }
+    let edges: Vec<_> = graph.edges().collect();
+    for e in edges {
+        graph.edge_weight_mut(e).since_year -= 1;
+    }
+}
+
+/// Build and edit a petgraph Graph through GraphMut.
+#[test]
+fn test_graph_mut_petgraph() {
+    let mut graph = petgraph::Graph::new();
+    let nodes = build_friends(&mut graph);
+    assert_eq!(graph.count_nodes(), 3);
+    assert_eq!(graph.count_edges(), 3);
+
+    age_everybody(&mut graph);
+    assert_eq!(Graph::node_weight(&graph, nodes[0]).age, 100);
+    assert!(graph.edge_weights().all(|e| e.since_year < 2020));
+
+    let stefan = GraphMut::remove_node(&mut graph, nodes[1]).unwrap();
+    assert_eq!(stefan.name, "stefan");
+    assert_eq!(graph.count_nodes(), 2);
+    assert_eq!(graph.count_edges(), 1);
+
+    let edge = Graph::edges(&graph).next().unwrap();
+    assert_eq!(
+        GraphMut::remove_edge(&mut graph, edge),
+        Some(FriendOf::new(2019))
+    );
+    assert_eq!(GraphMut::remove_edge(&mut graph, edge), None);
+    assert_eq!(graph.count_edges(), 0);
+}
+
+/// Build and edit a StableGraph through GraphMut. References
+/// to the remaining nodes stay valid after removals.
+#[test]
+fn test_graph_mut_stable_graph() {
+    let mut graph = StableGraph::new();
+    let nodes = build_friends(&mut graph);
+    age_everybody(&mut graph);
+
+    assert!(GraphMut::remove_node(&mut graph, nodes[0]).is_some());
+    assert!(GraphMut::remove_node(&mut graph, nodes[0]).is_none());
+
+    assert_eq!(Graph::node_weight(&graph, nodes[1]).age, 10);
+    assert_eq!(Graph::node_weight(&graph, nodes[2]).age, 37);
+    assert_eq!(graph.count_edges(), 2);
+    assert_eq!(graph.incoming_edges(nodes[2]).count(), 1);
+}
+
+/// Mutable access to removed nodes is rejected.
+#[test]
+#[should_panic(expected = "Couldn't find node weight: Node reference invalid.")]
+fn test_graph_mut_invalid_node() {
+    let mut graph = StableGraph::new();
+    let nodes = build_friends(&mut graph);
+    GraphMut::remove_node(&mut graph, nodes[2]);
+    GraphMut::node_weight_mut(&mut graph, nodes[2]);
+}
+
+/// Edges are only added with a direction the graph can hold.
+#[test]
+fn test_graph_mut_edge_direction() {
+    let mut graph = StableGraph::new();
+    let nodes = build_friends(&mut graph);
+    let (t, s) = (nodes[0], nodes[1]);
+
+    let edge = graph.try_add_edge_with_direction(t, s, FriendOf::new(2021), true);
+    assert_eq!(graph.adjacent_nodes(edge.unwrap()), (t, s));
+    let edge = graph.try_add_edge_with_direction(s, t, FriendOf::new(2021), false);
+    assert_eq!(edge, None);
+    assert_eq!(graph.count_edges(), 4);
+}
+
+/// Build and edit a GraphMap through GraphMut, where node weights are the keys.
+#[test]
+fn test_graph_mut_graph_map() {
+    let mut graph = UnGraphMap::<u32, &str>::new();
+    let a = GraphMut::add_node(&mut graph, 1);
+    let b = GraphMut::add_node(&mut graph, 2);
+    assert_eq!(GraphMut::add_node(&mut graph, 1), a);
+    assert_eq!(graph.count_nodes(), 2);
+
+    // Edges of undirected graphs are referenced by their sorted endpoints.
+    let edge = GraphMut::add_edge(&mut graph, b, a, "x");
+    assert_eq!(edge, (1, 2));
+    assert_eq!(GraphMut::add_edge(&mut graph, a, b, "y"), edge);
+    assert_eq!(graph.count_edges(), 1);
+    *GraphMut::edge_weight_mut(&mut graph, edge) = "z";
+    assert_eq!(*Graph::edge_weight(&graph, edge), "z");
+
+    assert_eq!(GraphMut::remove_edge(&mut graph, edge), Some("z"));
+    assert_eq!(GraphMut::remove_edge(&mut graph, edge), None);
+    assert_eq!(GraphMut::remove_node(&mut graph, b), Some(2));
+    assert_eq!(GraphMut::remove_node(&mut graph, b), None);
+    assert_eq!(Graph::nodes(&graph).collect::<Vec<_>>(), vec![1]);
+}
+
+/// GraphMap keys can't be changed in place.
+#[test]
+#[should_panic(expected = "Couldn't borrow node weight mutably: GraphMap node weights are keys.")]
+fn test_graph_mut_graph_map_node_weight() {
+    let mut graph = DiGraphMap::<u32, ()>::new();
+    GraphMut::add_node(&mut graph, 1);
+    GraphMut::node_weight_mut(&mut graph, 1);
+}
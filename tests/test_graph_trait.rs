@@ -168,6 +168,9 @@ fn check_undirected_edges() {
         assert_eq!(actual_routes, outgoing_edges);
         assert_eq!(actual_routes, incoming_edges);
         assert_eq!(actual_routes, adjacent_edges);
+        assert_eq!(graph.degree(*station_idx), actual_routes.len());
+        assert_eq!(graph.in_degree(*station_idx), actual_routes.len());
+        assert_eq!(graph.out_degree(*station_idx), actual_routes.len());
     }
 
     for (e_idx, (f, t, _)) in routes.iter() {
@@ -201,6 +204,9 @@ fn trial_and_error() {
     assert_eq!(graph.adjacent_edges(tobias).count(), 1);
     assert_eq!(graph.outgoing_edges(tobias).count(), 1);
     assert_eq!(graph.incoming_edges(tobias).count(), 0);
+    assert_eq!(graph.degree(tobias), 1);
+    assert_eq!(graph.out_degree(tobias), 1);
+    assert_eq!(graph.in_degree(tobias), 0);
 
     let tobi_and_horst = graph.adjacent_edges(tobias).next().unwrap();
 
@@ -209,3 +215,26 @@ fn trial_and_error() {
     assert!(graph.is_directed_edge(tobi_and_horst));
     assert_eq!(*graph.edge_weight(tobi_and_horst), x);
 }
+
+/// Tests that the fallible lookups reject invalid references instead of panicking.
+#[test]
+fn try_lookups() {
+    let graph = into_trait_object(make_sample_graph_variant());
+    let (node, wrong_node) = (NodeIndex::from(0), NodeIndex::from(42));
+    let (edge, wrong_edge) = (EdgeIndex::from(0), EdgeIndex::from(42));
+
+    assert!(graph.contains_node(node));
+    assert!(!graph.contains_node(wrong_node));
+    assert!(graph.contains_edge(edge));
+    assert!(!graph.contains_edge(wrong_edge));
+
+    assert_eq!(graph.try_node_weight(node), Some(graph.node_weight(node)));
+    assert_eq!(graph.try_node_weight(wrong_node), None);
+    assert_eq!(graph.try_edge_weight(edge), Some(graph.edge_weight(edge)));
+    assert_eq!(graph.try_edge_weight(wrong_edge), None);
+    assert_eq!(
+        graph.try_adjacent_nodes(edge),
+        Some(graph.adjacent_nodes(edge))
+    );
+    assert_eq!(graph.try_adjacent_nodes(wrong_edge), None);
+}
@@ -0,0 +1,167 @@
+use graphfind_rs::{
+    formats::{read_graphml, write_graphml},
+    graph::{Graph as RQLGraph, GraphIoError},
+    mixed_graph::MixedGraph,
+    pattern_matching::is_isomorphic,
+};
+use petgraph::{
+    graph::{DiGraph, UnGraph},
+    stable_graph::StableGraph,
+};
+use serde::Deserialize;
+
+pub mod common;
+use common::{make_sample_graph_undirected, make_sample_graph_variant, FriendOf, Person};
+
+/// Check that graphs with struct and enum weights survive writing and reading.
+#[test]
+fn test_graphml_round_trip() {
+    let graph = make_sample_graph_variant();
+    let mut buffer = vec![];
+    write_graphml(&graph, &mut buffer).unwrap();
+
+    let read: DiGraph<Person, FriendOf> = read_graphml(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+
+    let read: StableGraph<Person, FriendOf> = read_graphml(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+}
+
+/// Check the output for undirected graphs with plain weights.
+#[test]
+fn test_graphml_write_undirected() {
+    let (graph, _, _) = make_sample_graph_undirected();
+    let mut buffer = vec![];
+    write_graphml(&graph, &mut buffer).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+
+    assert!(text.contains(r#"<graph id="G" edgedefault="undirected">"#));
+    assert!(text.contains(r#"<key id="d0" for="node" attr.name="weight" attr.type="string"/>"#));
+    assert!(text.contains(r#"<key id="d1" for="edge" attr.name="weight" attr.type="long"/>"#));
+    assert!(text.contains(r#"<data key="d0">Böflingen</data>"#));
+    assert!(text.contains(r#"<edge id="e0" source="n0" target="n1">"#));
+
+    let read: UnGraph<String, i32> = read_graphml(text.as_bytes()).unwrap();
+    assert!(is_isomorphic(&graph, &read, |a, b| a == b, i32::eq).is_some());
+    // Directed graphs can't be read from undirected files.
+    let err = read_graphml::<DiGraph<String, i32>, _, _, _>(text.as_bytes()).unwrap_err();
+    assert!(matches!(err, GraphIoError::Schema { .. }), "{err}");
+}
+
+/// A station, as stored by other tools.
+#[derive(Deserialize, Debug, PartialEq)]
+struct Station {
+    label: String,
+    zone: i64,
+    accessible: Option<bool>,
+}
+
+/// Check reading files written by other tools, with defaults and edges referring to later nodes.
+#[test]
+fn test_graphml_read_foreign() {
+    let text = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="label" for="node" attr.name="label" attr.type="string"/>
+  <key id="zone" for="node" attr.name="zone" attr.type="int">
+    <default>1</default>
+  </key>
+  <key id="acc" for="node" attr.name="accessible" attr.type="boolean"/>
+  <key id="w" for="edge" attr.name="weight" attr.type="double"/>
+  <graph edgedefault="directed">
+    <edge source="a" target="b"><data key="w">2.5</data></edge>
+    <node id="a"><data key="label">Ehinger Tor &amp; more</data><data key="acc">true</data></node>
+    <node id="b"><data key="label">Theater</data><data key="zone">2</data></node>
+  </graph>
+</graphml>"#;
+    let graph: DiGraph<Station, f64> = read_graphml(text.as_bytes()).unwrap();
+
+    let stations: Vec<_> = graph.node_weights().collect();
+    assert_eq!(
+        stations,
+        vec![
+            &Station {
+                label: "Ehinger Tor & more".to_string(),
+                zone: 1,
+                accessible: Some(true)
+            },
+            &Station {
+                label: "Theater".to_string(),
+                zone: 2,
+                accessible: None
+            }
+        ]
+    );
+    assert_eq!(graph.edge_weights().collect::<Vec<_>>(), vec![&2.5]);
+}
+
+/// Check that invalid files are rejected, with the line of the problem.
+#[test]
+fn test_graphml_read_errors() {
+    let read = |text: &str| read_graphml::<DiGraph<(), ()>, _, _, _>(text.as_bytes()).unwrap_err();
+
+    let dangling =
+        "<graphml><graph>\n<node id=\"a\"/>\n<edge source=\"a\" target=\"b\"/>\n</graph></graphml>";
+    assert!(matches!(
+        read(dangling),
+        GraphIoError::DanglingEdge { line: Some(3), node } if node == "b"
+    ));
+    let undeclared =
+        "<graphml><graph>\n<node id=\"a\">\n  <data key=\"x\">1</data>\n</node></graph></graphml>";
+    let err = read(undeclared);
+    assert!(
+        matches!(err, GraphIoError::Schema { line: Some(3), .. }),
+        "{err}"
+    );
+    assert!(err.to_string().contains("undeclared key \"x\""), "{err}");
+    let truncated = "<graphml><graph>\n<node id=\"a\">";
+    assert!(matches!(
+        read(truncated),
+        GraphIoError::Syntax { line: 2, .. }
+    ));
+    let malformed = "<graphml>\n<graph>\n<node id=\"a\"></edge>";
+    assert!(matches!(
+        read(malformed),
+        GraphIoError::Syntax { line: 3, .. }
+    ));
+    assert!(matches!(
+        read("<graphml/>"),
+        GraphIoError::Schema { line: None, .. }
+    ));
+}
+
+/// Check that mixed graphs mark edges that differ from the graph's direction, and can be read back.
+#[test]
+fn test_graphml_mixed_round_trip() {
+    let mut graph = MixedGraph::new();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    graph.add_directed_edge(a, b, ());
+    graph.add_undirected_edge(a, b, ());
+    let mut buffer = vec![];
+    write_graphml(&graph, &mut buffer).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+
+    assert!(graph.is_directed());
+    assert!(text.contains(r#"<edge id="e0" source="n0" target="n1"/>"#));
+    assert!(text.contains(r#"<edge id="e1" source="n0" target="n1" directed="false"/>"#));
+    assert!(!text.contains("<key"));
+
+    let read: MixedGraph<(), ()> = read_graphml(text.as_bytes()).unwrap();
+    let directions: Vec<_> = read.edges().map(|e| read.is_directed_edge(e)).collect();
+    assert_eq!(directions, vec![true, false]);
+    let mut buffer = vec![];
+    write_graphml(&read, &mut buffer).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), text);
+
+    // Undirected edges can't be read into directed graphs.
+    let err = read_graphml::<DiGraph<(), ()>, _, _, _>(text.as_bytes()).unwrap_err();
+    assert!(matches!(err, GraphIoError::Schema { .. }), "{err}");
+
+    // Undirected files are read into mixed graphs as undirected edges.
+    let (graph, _, _) = make_sample_graph_undirected();
+    let mut buffer = vec![];
+    write_graphml(&graph, &mut buffer).unwrap();
+    let read: MixedGraph<String, i32> = read_graphml(buffer.as_slice()).unwrap();
+    assert!(read.edges().all(|e| !read.is_directed_edge(e)));
+    assert!(is_isomorphic(&graph, &read, |a, b| a == b, i32::eq).is_some());
+}
@@ -0,0 +1,139 @@
+use graphfind_rs::{
+    csr_graph::CsrGraph, filter_map::FilterMap, graph::Graph, mixed_graph::MixedGraph,
+    pattern_matching::is_isomorphic,
+};
+use petgraph::graph::{DiGraph, UnGraph};
+
+pub mod common;
+use common::{make_sample_graph_variant, Person};
+
+/// Creates a directed cycle of `len` nodes, with the node weights
+/// starting at `first` and all edge weights set to 1.
+fn make_cycle(len: u32, first: u32) -> DiGraph<u32, u32> {
+    let mut graph = DiGraph::new();
+    let nodes: Vec<_> = (0..len)
+        .map(|i| graph.add_node((first + i) % len))
+        .collect();
+    for i in 0..nodes.len() {
+        graph.add_edge(nodes[i], nodes[(i + 1) % nodes.len()], 1);
+    }
+    graph
+}
+
+/// Check that graphs are isomorphic to copies in other backends,
+/// and that the returned bijections connect matching elements.
+#[test]
+fn test_isomorphic_across_backends() {
+    let graph = make_sample_graph_variant();
+    let copy = CsrGraph::from_graph(&graph);
+
+    let iso = is_isomorphic(&graph, &copy, Person::eq, |a, b| a == b).unwrap();
+    assert_eq!(iso.nodes.len(), 4);
+    assert_eq!(iso.edges.len(), 4);
+    for (n, m) in &iso.nodes {
+        assert_eq!(Graph::node_weight(&graph, *n), copy.node_weight(*m));
+    }
+    for (e, e2) in &iso.edges {
+        let (a, b) = graph.adjacent_nodes(*e);
+        assert_eq!(copy.adjacent_nodes(*e2), (iso.nodes[&a], iso.nodes[&b]));
+        assert_eq!(Graph::edge_weight(&graph, *e), copy.edge_weight(*e2));
+    }
+}
+
+/// Check that weights and edge directions are compared.
+#[test]
+fn test_not_isomorphic() {
+    let cycle = make_cycle(4, 0);
+    assert!(is_isomorphic(&cycle, &make_cycle(4, 2), u32::eq, u32::eq).is_some());
+    assert!(is_isomorphic(&cycle, &make_cycle(5, 0), u32::eq, u32::eq).is_none());
+
+    let mut reversed = cycle.clone();
+    reversed.reverse();
+    assert!(is_isomorphic(&cycle, &reversed, u32::eq, u32::eq).is_none());
+    assert!(is_isomorphic(&cycle, &reversed, |_, _| true, u32::eq).is_some());
+
+    let mut heavier = cycle.clone();
+    heavier.edge_weights_mut().for_each(|w| *w = 2);
+    assert!(is_isomorphic(&cycle, &heavier, u32::eq, u32::eq).is_none());
+    assert!(is_isomorphic(&cycle, &heavier, u32::eq, |a, b| a < b).is_some());
+}
+
+/// Check that parallel edges are matched one to one, even if several pairings fit some of them.
+#[test]
+fn test_isomorphic_parallel_edges() {
+    let mut g1 = DiGraph::<(), u32>::new();
+    let (a, b) = (g1.add_node(()), g1.add_node(()));
+    let first = g1.add_edge(a, b, 1);
+    let second = g1.add_edge(a, b, 2);
+
+    let mut g2 = DiGraph::<(), u32>::new();
+    let (c, d) = (g2.add_node(()), g2.add_node(()));
+    let third = g2.add_edge(c, d, 2);
+    let fourth = g2.add_edge(c, d, 3);
+
+    // The first edge fits both edges of g2, the second one only the fourth.
+    let iso = is_isomorphic(&g1, &g2, |_, _| true, |w1, w2| w1 < w2).unwrap();
+    assert_eq!(iso.edges[&first], third);
+    assert_eq!(iso.edges[&second], fourth);
+
+    g2.remove_edge(fourth);
+    g2.add_edge(d, c, 3);
+    assert!(is_isomorphic(&g1, &g2, |_, _| true, |_, _| true).is_none());
+}
+
+/// Check that directed edges are only matched to directed edges, and undirected to undirected.
+#[test]
+fn test_isomorphic_mixed_edges() {
+    let mut undirected = UnGraph::<&str, ()>::new_undirected();
+    let x = undirected.add_node("a");
+    let y = undirected.add_node("b");
+    undirected.add_edge(x, y, ());
+
+    let mut mixed = MixedGraph::new();
+    let a = mixed.add_node("a");
+    let b = mixed.add_node("b");
+    mixed.add_directed_edge(a, b, ());
+    assert!(is_isomorphic(&undirected, &mixed, |x, y| x == y, |_, _| true).is_none());
+
+    let mut mixed = MixedGraph::new();
+    let b = mixed.add_node("b");
+    let a = mixed.add_node("a");
+    mixed.add_undirected_edge(b, a, ());
+    let iso = is_isomorphic(&undirected, &mixed, |x, y| x == y, |_, _| true).unwrap();
+    assert_eq!(iso.nodes[&x], a);
+    assert_eq!(iso.nodes[&y], b);
+}
+
+/// Check that filtered views can be compared, e.g. to deduplicate results.
+#[test]
+fn test_isomorphic_filter_maps() {
+    let graph = make_cycle(6, 0);
+    let even = FilterMap::weight_filter(&graph, |n| n % 2 == 0, |_| true);
+    let odd = FilterMap::weight_filter(&graph, |n| n % 2 == 1, |_| true);
+    assert_eq!(even.count_edges(), 0);
+    assert!(is_isomorphic(&even, &odd, |_, _| true, |_, _| true).is_some());
+    assert!(is_isomorphic(&even, &odd, |n, m| **n + 1 == **m, |_, _| true).is_some());
+    assert!(is_isomorphic(&even, &odd, |n, m| n == m, |_, _| true).is_none());
+}
+
+/// Check that the search stops at the first isomorphism, even if there are very many of them.
+#[test]
+fn test_isomorphic_symmetric_graphs() {
+    let mut g1 = UnGraph::<(), ()>::new_undirected();
+    let mut g2 = UnGraph::<(), ()>::new_undirected();
+    for _ in 0..20 {
+        g1.add_node(());
+        g2.add_node(());
+    }
+    assert!(is_isomorphic(&g1, &g2, |_, _| true, |_, _| true).is_some());
+    assert!(is_isomorphic(
+        &make_cycle(200, 0),
+        &make_cycle(200, 0),
+        |_, _| true,
+        u32::eq
+    )
+    .is_some());
+    assert!(is_isomorphic(&DiGraph::<(), ()>::new(), &g2, |_, _| true, |_, _| true).is_none());
+    let empty = UnGraph::<(), ()>::new_undirected();
+    assert!(is_isomorphic(&empty, &empty, |_, _| true, |_, _| true).is_some());
+}
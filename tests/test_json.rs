@@ -0,0 +1,148 @@
+use graphfind_rs::{
+    csr_graph::CsrGraph,
+    formats::{read_json, write_json, JsonEdge, JsonGraph, JsonNode},
+    graph::{Graph as QueryGraph, GraphIoError},
+    mixed_graph::MixedGraph,
+    pattern_matching::is_isomorphic,
+};
+use petgraph::{
+    graph::{DiGraph, UnGraph},
+    graphmap::UnGraphMap,
+    stable_graph::StableGraph,
+    Undirected,
+};
+
+pub mod common;
+use common::{make_sample_graph_variant, FriendOf, Person};
+
+/// Check that graphs saved from one backend can be loaded into others.
+#[test]
+fn test_json_round_trip() {
+    let graph = make_sample_graph_variant();
+    let mut buffer = vec![];
+    write_json(&graph, &mut buffer).unwrap();
+
+    let read: StableGraph<Person, FriendOf> = read_json(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+
+    let read: MixedGraph<Person, FriendOf> = read_json(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+
+    let mut second_buffer = vec![];
+    write_json(&read, &mut second_buffer).unwrap();
+    assert_eq!(buffer, second_buffer);
+}
+
+/// Check that a file saved from a petgraph can be loaded into every backend.
+#[test]
+fn test_json_read_into_every_backend() {
+    let mut graph = UnGraph::<u32, String>::new_undirected();
+    let nodes: Vec<_> = (1..=4).map(|i| graph.add_node(i)).collect();
+    graph.add_edge(nodes[0], nodes[1], "a".to_string());
+    graph.add_edge(nodes[1], nodes[2], "b".to_string());
+    graph.add_edge(nodes[3], nodes[1], "c".to_string());
+    let mut buffer = vec![];
+    write_json(&graph, &mut buffer).unwrap();
+
+    let read: UnGraph<u32, String> = read_json(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &read, u32::eq, String::eq).is_some());
+    let stable: StableGraph<u32, String, Undirected> = read_json(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &stable, u32::eq, String::eq).is_some());
+    let read: UnGraphMap<u32, String> = read_json(buffer.as_slice()).unwrap();
+    assert!(is_isomorphic(&graph, &read, u32::eq, String::eq).is_some());
+
+    // Mixed graphs hold the edges of undirected graphs as undirected edges.
+    let read: MixedGraph<u32, String> = read_json(buffer.as_slice()).unwrap();
+    assert!(read.edges().all(|e| !read.is_directed_edge(e)));
+    assert!(is_isomorphic(&graph, &read, u32::eq, String::eq).is_some());
+
+    // CSR graphs are built from a graph loaded into another backend.
+    let csr = CsrGraph::from_graph(&stable);
+    assert!(is_isomorphic(&graph, &csr, u32::eq, String::eq).is_some());
+}
+
+/// Check the written document, including edges whose direction differs from the graph.
+#[test]
+fn test_json_write() {
+    let mut graph = MixedGraph::new();
+    let ulm = graph.add_node("Ulm");
+    let neu_ulm = graph.add_node("Neu-Ulm");
+    graph.add_directed_edge(ulm, neu_ulm, 3);
+    graph.add_undirected_edge(neu_ulm, ulm, 4);
+
+    let mut buffer = vec![];
+    write_json(&graph, &mut buffer).unwrap();
+    let expected = r#"{"directed":true,"nodes":[{"id":0,"weight":"Ulm"},{"id":1,"weight":"Neu-Ulm"}],"edges":[{"source":0,"target":1,"weight":3},{"source":1,"target":0,"weight":4,"directed":false}]}"#;
+    assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+
+    // Undirected edges can't be read into directed graphs.
+    let err = read_json::<DiGraph<String, i32>, _, _, _>(expected.as_bytes()).unwrap_err();
+    assert!(matches!(err, GraphIoError::Schema { .. }), "{err}");
+
+    // Mixed graphs keep the direction of each edge.
+    let read: MixedGraph<String, i32> = read_json(expected.as_bytes()).unwrap();
+    let directions: Vec<_> = read.edges().map(|e| read.is_directed_edge(e)).collect();
+    assert_eq!(directions, vec![true, false]);
+    let mut buffer = vec![];
+    write_json(&read, &mut buffer).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+}
+
+/// Check reading hand-written documents with arbitrary node IDs.
+#[test]
+fn test_json_read() {
+    let text = r#"{
+        "directed": false,
+        "nodes": [{ "id": 7, "weight": "a" }, { "id": 3, "weight": "b" }],
+        "edges": [{ "source": 3, "target": 7, "weight": 1.5 }]
+    }"#;
+    let graph: UnGraph<String, f64> = read_json(text.as_bytes()).unwrap();
+    let (a, b) = ("a".to_string(), "b".to_string());
+    let expected = JsonGraph {
+        directed: false,
+        nodes: vec![
+            JsonNode { id: 0, weight: &a },
+            JsonNode { id: 1, weight: &b },
+        ],
+        edges: vec![JsonEdge {
+            source: 1,
+            target: 0,
+            weight: &1.5,
+            directed: None,
+        }],
+    };
+    assert_eq!(JsonGraph::from_graph(&graph), expected);
+}
+
+/// Check that invalid documents are rejected.
+#[test]
+fn test_json_read_errors() {
+    let read = |text: &str| read_json::<DiGraph<i32, ()>, _, _, _>(text.as_bytes());
+    for text in [
+        r#"{"directed":false,"nodes":[],"edges":[]}"#,
+        r#"{"directed":true,"nodes":[{"id":0,"weight":1},{"id":0,"weight":2}],"edges":[]}"#,
+        r#"{"directed":true,"nodes":[{"id":0,"weight":"x"}],"edges":[]}"#,
+        r#"{"directed":true,"nodes":[]}"#,
+    ] {
+        let err = read(text).unwrap_err();
+        assert!(matches!(err, GraphIoError::Schema { .. }), "{text}: {err}");
+    }
+    let dangling = r#"{"directed":true,"nodes":[{"id":0,"weight":1}],"edges":[{"source":0,"target":1,"weight":null}]}"#;
+    assert!(matches!(
+        read(dangling).unwrap_err(),
+        GraphIoError::DanglingEdge { line: None, node } if node == "1"
+    ));
+    let err = read("{\n  \"directed\": true,\n  \"nodes\": [").unwrap_err();
+    assert!(
+        matches!(
+            err,
+            GraphIoError::Syntax {
+                line: 3,
+                column: Some(12),
+                ..
+            }
+        ),
+        "{err}"
+    );
+    assert!(read(r#"{"directed":true,"nodes":[],"edges":[]}"#).is_ok());
+}
@@ -0,0 +1,121 @@
+use graphfind_rs::{
+    csr_graph::CsrGraph,
+    formats::write_json,
+    matcher,
+    pattern_matching::{
+        match_records, new_pattern, solve_vf_with_refs, write_matches_json, MatchRecord,
+        MatchedElement, PatternGraph,
+    },
+};
+use petgraph::graph::DiGraph;
+
+pub mod common;
+use common::{make_sample_graph_variant, new_professor, new_student, FriendOf, Person, Role};
+
+/// Check that matches are recorded with the positions of the pattern and base graph elements.
+#[test]
+fn test_match_records() {
+    let graph = make_sample_graph_variant();
+    let mut pattern = new_pattern();
+    let student = pattern.add_node(matcher!(Person {
+        role: Role::Student { .. },
+        ..
+    }));
+    let professor = pattern.add_node(matcher!(Person {
+        role: Role::Professor { .. },
+        ..
+    }));
+    pattern.add_edge(student, professor, matcher!(FriendOf { since_year: 2010 }));
+    let results = solve_vf_with_refs(&pattern, &graph);
+
+    let records = match_records(&pattern, &graph, &results);
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(
+        record.nodes[&0],
+        MatchedElement {
+            id: 2,
+            weight: &new_student("horst", 55, 823340)
+        }
+    );
+    assert_eq!(record.nodes[&1].id, 3);
+    assert_eq!(
+        record.edges[&0],
+        MatchedElement {
+            id: 1,
+            weight: &FriendOf::new(2010)
+        }
+    );
+}
+
+/// Check that elements with zero-sized weights, which share their address, are told apart.
+#[test]
+fn test_match_records_zero_sized_weights() {
+    let mut base = DiGraph::new();
+    let a = base.add_node(());
+    let b = base.add_node(());
+    let c = base.add_node(());
+    base.add_edge(a, b, ());
+    base.add_edge(b, c, ());
+    let graph = CsrGraph::from_graph(&base);
+
+    let mut pattern = new_pattern();
+    let from = pattern.add_node(matcher!());
+    let to = pattern.add_node(matcher!());
+    pattern.add_edge(from, to, matcher!());
+    let results = solve_vf_with_refs(&pattern, &graph);
+
+    let mut ids: Vec<_> = match_records(&pattern, &graph, &results)
+        .iter()
+        .map(|record| {
+            (
+                record.nodes[&0].id,
+                record.nodes[&1].id,
+                record.edges[&0].id,
+            )
+        })
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec![(0, 1, 0), (1, 2, 1)]);
+}
+
+/// Check that all matches are written into one JSON document, which can be read back.
+#[test]
+fn test_write_matches_json() {
+    let graph = make_sample_graph_variant();
+    let mut pattern = new_pattern();
+    let friend = pattern.add_node(matcher!());
+    let professor = pattern.add_node(matcher!(Person {
+        role: Role::Professor { .. },
+        ..
+    }));
+    // Hidden elements aren't part of the records.
+    pattern.add_hidden_edge(friend, professor, matcher!());
+    let results = solve_vf_with_refs(&pattern, &graph);
+    assert_eq!(results.len(), 2);
+
+    let mut buffer = vec![];
+    write_matches_json(&pattern, &graph, &results, &mut buffer).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert!(text.starts_with(r#"[{"nodes":{"0":{"id":"#), "{text}");
+
+    let mut read: Vec<MatchRecord<Person, FriendOf>> = serde_json::from_str(&text).unwrap();
+    read.sort_by_key(|record| record.nodes[&0].id);
+    let bettina = new_professor(
+        "bettina",
+        36,
+        "Faculty of Software Engineering and Programming Languages",
+    );
+    for (record, (id, name)) in read.iter().zip([(1, "stefan"), (2, "horst")]) {
+        assert_eq!(record.nodes[&0].id, id);
+        assert_eq!(record.nodes[&0].weight.name, name);
+        assert_eq!(record.nodes[&1].weight, bettina);
+        assert!(record.edges.is_empty());
+    }
+
+    // The IDs refer to the nodes written by write_json.
+    let mut buffer = vec![];
+    write_json(&graph, &mut buffer).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert!(text.contains(r#"{"id":1,"weight":{"name":"stefan""#));
+}
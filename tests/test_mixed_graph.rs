@@ -0,0 +1,110 @@
+use graphfind_rs::{
+    graph::{Graph, GraphMut},
+    matcher,
+    mixed_graph::{MixedGraph, MixedNodeIndex},
+    pattern_matching::{new_pattern, solve_vf, PatternGraph},
+};
+
+/// Relations of a small software model.
+#[derive(Debug, PartialEq, Eq)]
+enum Relation {
+    /// Directed.
+    Calls,
+    /// Undirected.
+    SiblingOf,
+}
+
+/// Creates a model with three functions:
+/// `main` calls `parse` and `print`, and `parse` and `print` are siblings.
+fn make_sample_mixed_graph() -> (MixedGraph<&'static str, Relation>, Vec<MixedNodeIndex>) {
+    let mut graph = MixedGraph::new();
+    let main = graph.add_node("main");
+    let parse = graph.add_node("parse");
+    let print = graph.add_node("print");
+    graph.add_directed_edge(main, parse, Relation::Calls);
+    graph.add_directed_edge(main, print, Relation::Calls);
+    graph.add_undirected_edge(parse, print, Relation::SiblingOf);
+    (graph, vec![main, parse, print])
+}
+
+/// Directed edges appear in one direction only, undirected edges in both.
+#[test]
+fn test_mixed_edge_directions() {
+    let (graph, nodes) = make_sample_mixed_graph();
+    let (main, parse, print) = (nodes[0], nodes[1], nodes[2]);
+
+    assert_eq!(graph.count_nodes(), 3);
+    assert_eq!(graph.count_edges(), 3);
+
+    assert_eq!(graph.outgoing_edges(main).count(), 2);
+    assert_eq!(graph.incoming_edges(main).count(), 0);
+    assert_eq!(graph.incoming_edges(parse).count(), 2);
+    assert_eq!(graph.outgoing_edges(parse).count(), 1);
+    assert_eq!(graph.adjacent_edges(print).count(), 2);
+    assert_eq!(graph.degree(print), 2);
+    assert_eq!(graph.in_degree(print), 2);
+    assert_eq!(graph.out_degree(print), 1);
+
+    for e in graph.edges() {
+        let directed = *graph.edge_weight(e) == Relation::Calls;
+        assert_eq!(graph.is_directed_edge(e), directed);
+    }
+}
+
+/// A directed pattern edge only matches directed edges in the same orientation,
+/// and undirected edges in both orientations.
+#[test]
+fn test_mixed_pattern_match() {
+    let (graph, _) = make_sample_mixed_graph();
+
+    let mut calls = new_pattern();
+    let caller = calls.add_node(matcher!());
+    let callee = calls.add_node(matcher!());
+    calls.add_edge(caller, callee, matcher!(Relation::Calls));
+    let results = solve_vf(&calls, &graph);
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| **r.node_weight(caller) == "main"));
+
+    let mut siblings = new_pattern();
+    let a = siblings.add_node(matcher!());
+    let b = siblings.add_node(matcher!());
+    siblings.add_edge(a, b, matcher!(Relation::SiblingOf));
+    let mut results: Vec<_> = solve_vf(&siblings, &graph)
+        .iter()
+        .map(|r| (**r.node_weight(a), **r.node_weight(b)))
+        .collect();
+    results.sort();
+    assert_eq!(results, vec![("parse", "print"), ("print", "parse")]);
+
+    // No function is called by one of its siblings.
+    let mut cycle = new_pattern();
+    let a = cycle.add_node(matcher!());
+    let b = cycle.add_node(matcher!());
+    cycle.add_edge(a, b, matcher!(Relation::Calls));
+    cycle.add_edge(b, a, matcher!(Relation::SiblingOf));
+    assert!(solve_vf(&cycle, &graph).is_empty());
+}
+
+/// Removing elements keeps the remaining references valid.
+#[test]
+fn test_mixed_graph_mut() {
+    let (mut graph, nodes) = make_sample_mixed_graph();
+    let (main, parse, print) = (nodes[0], nodes[1], nodes[2]);
+
+    assert_eq!(GraphMut::remove_node(&mut graph, parse), Some("parse"));
+    assert_eq!(GraphMut::remove_node(&mut graph, parse), None);
+    assert_eq!(graph.count_nodes(), 2);
+    assert_eq!(graph.count_edges(), 1);
+    assert_eq!(*graph.node_weight(print), "print");
+    assert_eq!(graph.incoming_edges(print).count(), 1);
+
+    let loop_edge = GraphMut::add_edge(&mut graph, main, main, Relation::Calls);
+    assert_eq!(graph.adjacent_edges(main).count(), 2);
+    assert_eq!(graph.incoming_edges(main).count(), 1);
+    *graph.node_weight_mut(main) = "start";
+    assert_eq!(graph.adjacent_nodes(loop_edge), (main, main));
+    assert_eq!(
+        graph.node_weights().copied().collect::<Vec<_>>(),
+        vec!["start", "print"]
+    );
+}
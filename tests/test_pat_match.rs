@@ -8,9 +8,11 @@ use common::{
     Relation,
     Relation::{Knows, PlaysIn, Successor},
 };
-use graphfind_rs::pattern_matching::solve_vf;
+use graphfind_rs::pattern_matching::{solve_vf, solve_vf_indexed};
 use graphfind_rs::{
-    graph::Graph as QueryGraph, matcher, pattern_matching::new_pattern,
+    graph::{Graph as QueryGraph, PropertyIndex},
+    matcher,
+    pattern_matching::new_pattern,
     pattern_matching::PatternGraph,
 };
 use petgraph::graph::{Graph, NodeIndex};
@@ -225,7 +227,7 @@ fn test_single_node_any_pattern() {
     for res in results {
         let indices = res.nodes().collect::<Vec<_>>();
         assert_eq!(1, indices.len());
-        let index = *indices.get(0).unwrap();
+        let index = *indices.first().unwrap();
         assert!(single_pattern.nodes().any(|i| i == index));
         found_indices.insert(index);
     }
@@ -514,7 +516,7 @@ fn test_node_edge_counts_terminate_early() {
 /// 1. Three different actors.
 /// 2. Three different movies.
 /// 3. Next to other relations, we have three persons who know each other,
-/// and also themselves.
+///    and also themselves.
 ///
 /// Assert 3 matches.
 #[test]
@@ -1327,3 +1329,92 @@ fn match_empty() {
     assert_eq!(0, query[0].count_nodes());
     assert_eq!(0, query[0].count_edges());
 }
+
+/// Match a directed edge pattern on an undirected graph.
+/// Each undirected edge is matched in both orientations.
+#[test]
+fn match_edge_in_undirected_graph() {
+    let mut graph = Graph::new_undirected();
+    let a = graph.add_node(1);
+    let b = graph.add_node(2);
+    let c = graph.add_node(3);
+    graph.add_edge(a, b, ());
+    graph.add_edge(c, b, ());
+
+    let mut pattern = new_pattern();
+    let from = pattern.add_node(matcher!());
+    let to = pattern.add_node(matcher!(2));
+    pattern.add_edge(from, to, matcher!());
+
+    let mut results: Vec<_> = solve_vf(&pattern, &graph)
+        .iter()
+        .map(|r| (**r.node_weight(from), **r.node_weight(to)))
+        .collect();
+    results.sort();
+    assert_eq!(results, vec![(1, 2), (3, 2)]);
+}
+
+/// Match parallel pattern edges. Like before degree pruning, both may be matched
+/// to the same base edge, so a node with a single outgoing edge still matches.
+#[test]
+fn match_parallel_pattern_edges() {
+    let mut graph = Graph::new();
+    let a = graph.add_node(1);
+    let b = graph.add_node(2);
+    let c = graph.add_node(3);
+    graph.add_edge(a, b, 10);
+    graph.add_edge(b, c, 20);
+
+    let mut pattern = new_pattern();
+    let from = pattern.add_node(matcher!(1));
+    let to = pattern.add_node(matcher!());
+    let first = pattern.add_edge(from, to, matcher!());
+    let second = pattern.add_edge(from, to, matcher!(10));
+
+    let results = solve_vf(&pattern, &graph);
+    assert_eq!(results.len(), 1);
+    assert_eq!(**results[0].node_weight(to), 2);
+    assert_eq!(**results[0].edge_weight(first), 10);
+    assert_eq!(**results[0].edge_weight(second), 10);
+}
+
+/// Looks up the name of persons, used as index key.
+fn person_name(node: &MovieNode) -> Option<String> {
+    match node {
+        MovieNode::Person(p) => Some(p.name.clone()),
+        _ => None,
+    }
+}
+
+/// Find the persons that stefan and fabian know, starting the search
+/// from the indexed nodes. The indexed lookup restricts the pattern nodes
+/// just like an equivalent matcher would.
+#[test]
+fn match_indexed_persons() {
+    let data_graph = full_graph().0;
+    let index = PropertyIndex::new(&data_graph, person_name);
+    assert_eq!(index.count_keys(), 5);
+    assert!(index.get(&"nobody".to_string()).is_empty());
+
+    let mut pattern_graph = new_pattern();
+    let p1 = pattern_graph.add_node(is_person);
+    let p2 = pattern_graph.add_node(is_person);
+    pattern_graph.add_edge(p1, p2, matcher!(Knows));
+
+    let lookups = HashMap::from([(p1, "stefan".to_string())]);
+    let results = solve_vf_indexed(&pattern_graph, &data_graph, &index, &lookups);
+    assert_eq!(results.len(), 1);
+    assert!(check_for_actor(results[0].node_weight(p2), "yves"));
+
+    // Pattern nodes reached from other nodes are restricted as well.
+    let lookups = HashMap::from([(p2, "fabian".to_string())]);
+    let results = solve_vf_indexed(&pattern_graph, &data_graph, &index, &lookups);
+    let friends: HashSet<_> = results
+        .iter()
+        .filter_map(|r| person_name(r.node_weight(p1)))
+        .collect();
+    assert_eq!(
+        friends,
+        HashSet::from(["yves".to_string(), "tobias".to_string()])
+    );
+}
@@ -0,0 +1,102 @@
+use graphfind_rs::{
+    algorithms::{astar, dijkstra, reachable_within, Direction},
+    filter_map::FilterMap,
+    graph::Graph as QueryGraph,
+};
+use petgraph::graph::{NodeIndex, UnGraph};
+
+/// Creates an undirected friendship graph between five people.
+/// Edge weights are the years in which the friendships began.
+fn make_friendship_graph() -> (UnGraph<&'static str, u32>, Vec<NodeIndex>) {
+    let mut graph = UnGraph::new_undirected();
+    let nodes: Vec<_> = ["ada", "bob", "cyd", "dan", "eve"]
+        .into_iter()
+        .map(|name| graph.add_node(name))
+        .collect();
+    let (ada, bob, cyd, dan, eve) = (nodes[0], nodes[1], nodes[2], nodes[3], nodes[4]);
+    graph.add_edge(ada, bob, 2005);
+    graph.add_edge(bob, eve, 2012);
+    graph.add_edge(ada, cyd, 2015);
+    graph.add_edge(cyd, dan, 2018);
+    graph.add_edge(dan, eve, 2011);
+    (graph, nodes)
+}
+
+/// Find the shortest path between two people, using only friendships that began after 2010.
+#[test]
+fn test_dijkstra_on_view() {
+    let (graph, nodes) = make_friendship_graph();
+    let (ada, eve) = (nodes[0], nodes[4]);
+
+    let path = dijkstra(&graph, ada, eve, Direction::Outgoing, |_| 1).unwrap();
+    assert_eq!(path.nodes, vec![ada, nodes[1], eve]);
+    assert_eq!(path.cost, 2);
+
+    let recent = FilterMap::weight_filter(&graph, |_| true, |year| *year > 2010);
+    let path = dijkstra(&recent, ada, eve, Direction::Outgoing, |_| 1).unwrap();
+    assert_eq!(path.nodes, vec![ada, nodes[2], nodes[3], eve]);
+    assert_eq!(path.cost, 3);
+    for (i, e) in path.edges.iter().enumerate() {
+        let (a, b) = recent.adjacent_nodes(*e);
+        assert!(
+            (a, b) == (path.nodes[i], path.nodes[i + 1])
+                || (b, a) == (path.nodes[i], path.nodes[i + 1])
+        );
+    }
+
+    let ancient = FilterMap::weight_filter(&graph, |_| true, |year| *year < 2010);
+    assert!(dijkstra(&ancient, ada, eve, Direction::Outgoing, |_| 1).is_none());
+
+    let trivial = dijkstra(&graph, eve, eve, Direction::Outgoing, |_| 1).unwrap();
+    assert_eq!(trivial.nodes, vec![eve]);
+    assert!(trivial.edges.is_empty());
+}
+
+/// Use the age of friendships as cost, and guide the search with a trivial estimate.
+#[test]
+fn test_astar() {
+    let (graph, nodes) = make_friendship_graph();
+    let (ada, eve) = (nodes[0], nodes[4]);
+
+    // Prefer young friendships, measured as years before 2020.
+    let age = |year: &u32| f64::from(2020 - year);
+    let path = astar(&graph, ada, eve, Direction::Both, age, |n| {
+        if n == eve {
+            0.0
+        } else {
+            1.0
+        }
+    })
+    .unwrap();
+    assert_eq!(path.nodes, vec![ada, nodes[2], nodes[3], eve]);
+    assert_eq!(path.cost, 5.0 + 2.0 + 9.0);
+    assert_eq!(
+        dijkstra(&graph, ada, eve, Direction::Both, age).unwrap(),
+        path
+    );
+}
+
+/// Check reachability on a directed graph, in both directions.
+#[test]
+fn test_reachable_within() {
+    let mut graph = petgraph::graph::DiGraph::<u32, ()>::new();
+    let chain: Vec<_> = (0..5).map(|i| graph.add_node(i)).collect();
+    for pair in chain.windows(2) {
+        graph.add_edge(pair[0], pair[1], ());
+    }
+    graph.add_edge(chain[0], chain[3], ());
+
+    let reachable = reachable_within(&graph, chain[0], 2, Direction::Outgoing);
+    assert_eq!(reachable.len(), 5);
+    assert_eq!(reachable[&chain[3]], 1);
+    assert_eq!(reachable[&chain[4]], 2);
+
+    let reachable = reachable_within(&graph, chain[2], 1, Direction::Outgoing);
+    assert_eq!(reachable.len(), 2);
+    let reachable = reachable_within(&graph, chain[2], 5, Direction::Incoming);
+    assert_eq!(reachable.len(), 3);
+    assert_eq!(
+        reachable_within(&graph, chain[2], 0, Direction::Both).len(),
+        1
+    );
+}
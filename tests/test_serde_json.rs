@@ -1,4 +1,4 @@
-use graphfind_rs::graph::GraphReadWriter;
+use graphfind_rs::graph::{GraphIoError, GraphReadWriter};
 use petgraph::{
     graph::Graph,
     visit::{EdgeRef, IntoNodeReferences},
@@ -56,20 +56,19 @@ fn test_serde_file_not_exists() {
     let dir = TestDir::temp().create(EMPTY_FILE_NAME, test_dir::FileType::EmptyFile);
 
     // Fail to serialize from a file that doesn't exist.
-    let read_attempt: Result<Box<Graph<Person, FriendOf, Directed, _>>, std::io::Error> =
+    let read_attempt: Result<Box<Graph<Person, FriendOf, Directed, _>>, GraphIoError> =
         GraphReadWriter::deserialize_graph_from_file(&append_path(&dir, MISSING_NAME));
     let err = read_attempt.expect_err("Read attempt from missing file should fail.");
 
     // Default Rust Error to expect
-    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(matches!(err, GraphIoError::Io(e) if e.kind() == std::io::ErrorKind::NotFound));
 
     // Create an empty file and try to deserialize from it.
-    let read_attempt: Result<Box<Graph<Person, FriendOf, Directed, _>>, std::io::Error> =
+    let read_attempt: Result<Box<Graph<Person, FriendOf, Directed, _>>, GraphIoError> =
         GraphReadWriter::deserialize_graph_from_file(&append_path(&dir, EMPTY_FILE_NAME));
     let err = read_attempt.expect_err("Read from empty file should fail.");
 
-    assert_eq!(err.kind(), std::io::ErrorKind::Other);
-    assert!(err.into_inner().is_some());
+    assert!(matches!(err, GraphIoError::Syntax { line: 1, .. }));
 }
 
 #[test]
@@ -82,7 +81,41 @@ fn test_write_error_nonexistent_dir() {
     dbg!(&write_attempt);
 
     let err = write_attempt.expect_err("Write to nonexistent dir should fail");
-    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(matches!(err, GraphIoError::Io(e) if e.kind() == std::io::ErrorKind::NotFound));
+}
+
+/// Test reading and writing graphs from and to in-memory buffers.
+#[test]
+fn test_serde_json_stream_read_write() {
+    let graph_back = make_sample_graph_variant();
+
+    let mut buffer = vec![];
+    graph_back.write_graph(&mut buffer).unwrap();
+    let graph: Box<Graph<Person, FriendOf, Directed, _>> =
+        GraphReadWriter::read_graph(buffer.as_slice()).unwrap();
+
+    assert_eq!(graph_back.node_count(), graph.node_count());
+    for edge_ref in graph_back.edge_references() {
+        assert_eq!(
+            graph.edge_endpoints(EdgeRef::id(&edge_ref)),
+            Some((edge_ref.source(), edge_ref.target()))
+        );
+    }
+
+    // Embedded data is read as well.
+    let graph: Box<Graph<u32, (), Directed, _>> = GraphReadWriter::read_graph(
+        br#"{"nodes":[1,2],"node_holes":[],"edge_property":"directed","edges":[[0,1,null]]}"#
+            .as_slice(),
+    )
+    .unwrap();
+    assert_eq!(graph.node_weights().sum::<u32>(), 3);
+    assert_eq!(graph.edge_count(), 1);
+
+    // Truncated data can't be read.
+    let read_attempt: Result<Box<Graph<Person, FriendOf, Directed, _>>, GraphIoError> =
+        GraphReadWriter::read_graph(&buffer[..buffer.len() / 2]);
+    let err = read_attempt.expect_err("Read from truncated buffer should fail.");
+    assert!(matches!(err, GraphIoError::Syntax { .. }));
 }
 
 fn append_path(dir: &TestDir, path: &str) -> String {
@@ -0,0 +1,129 @@
+use graphfind_rs::{
+    graph::{Graph as QueryGraph, GraphReadWriter, VizDotGraph},
+    matcher,
+    pattern_matching::{new_pattern, solve_vf, PatternGraph},
+};
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use test_dir::{DirBuilder, TestDir};
+
+pub mod common;
+use common::{new_professor, new_student, FriendOf, Person};
+
+/// Creates a stable graph with four persons and removes one of them,
+/// leaving a gap in the node indices.
+fn make_sample_stable_graph() -> (StableGraph<Person, FriendOf>, Vec<NodeIndex>) {
+    let mut graph = StableGraph::new();
+    let t = graph.add_node(new_student("tobias", 99, 900000));
+    let s = graph.add_node(new_student("stefan", 9, 89000));
+    let h = graph.add_node(new_student("horst", 55, 823340));
+    let b = graph.add_node(new_professor(
+        "bettina",
+        36,
+        "Faculty of Software Engineering",
+    ));
+
+    graph.add_edge(t, h, FriendOf::new(2020));
+    graph.add_edge(s, h, FriendOf::new(2010));
+    graph.add_edge(h, b, FriendOf::new(2010));
+    graph.add_edge(s, b, FriendOf::new(2018));
+
+    graph.remove_node(t);
+    (graph, vec![s, h, b])
+}
+
+/// Check that removed nodes and their edges no longer appear, and that
+/// the remaining references stay valid.
+#[test]
+fn test_stable_graph_removal() {
+    let (graph, remaining) = make_sample_stable_graph();
+
+    assert_eq!(graph.count_nodes(), 3);
+    assert_eq!(graph.count_edges(), 3);
+    assert_eq!(QueryGraph::nodes(&graph).collect::<Vec<_>>(), remaining);
+    assert_eq!(QueryGraph::edges(&graph).count(), 3);
+
+    let (s, h, b) = (remaining[0], remaining[1], remaining[2]);
+    assert_eq!(QueryGraph::node_weight(&graph, s).name, "stefan");
+    assert_eq!(QueryGraph::node_weight(&graph, b).name, "bettina");
+    assert_eq!(graph.incoming_edges(h).count(), 1);
+    assert_eq!(graph.outgoing_edges(s).count(), 2);
+    assert_eq!(graph.adjacent_edges(h).count(), 2);
+
+    for e in QueryGraph::edges(&graph) {
+        let (a, b) = graph.adjacent_nodes(e);
+        assert!(remaining.contains(&a) && remaining.contains(&b));
+        assert!(graph.is_directed_edge(e));
+    }
+}
+
+/// Match a two node pattern against a stable graph with removed nodes.
+#[test]
+fn test_stable_graph_pattern_match() {
+    let (graph, _) = make_sample_stable_graph();
+
+    let mut pattern = new_pattern();
+    let student = pattern.add_node(matcher!(Person { .. }));
+    let friend = pattern.add_node(matcher!(Person { .. }));
+    pattern.add_edge(student, friend, matcher!(FriendOf { since_year: 2010 }));
+
+    let results = solve_vf(&pattern, &graph);
+    assert_eq!(results.len(), 2);
+
+    let mut names: Vec<_> = results
+        .iter()
+        .map(|result| {
+            assert_eq!(result.count_edges(), 1);
+            result.node_weight(student).name.clone() + "->" + &result.node_weight(friend).name
+        })
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["horst->bettina", "stefan->horst"]);
+}
+
+/// Use a StableGraph as pattern graph.
+#[test]
+fn test_stable_graph_as_pattern() {
+    let (graph, _) = make_sample_stable_graph();
+
+    let mut pattern = StableGraph::new();
+    let removed = PatternGraph::<Person, FriendOf>::add_node(&mut pattern, matcher!());
+    let from = PatternGraph::<Person, FriendOf>::add_node(&mut pattern, matcher!());
+    let to = PatternGraph::<Person, FriendOf>::add_node(&mut pattern, matcher!());
+    PatternGraph::add_edge(&mut pattern, from, to, matcher!());
+    pattern.remove_node(removed);
+
+    let results = solve_vf(&pattern, &graph);
+    assert_eq!(results.len(), 3);
+}
+
+/// Serialize a stable graph with holes, and read it back.
+#[test]
+fn test_stable_graph_read_write() {
+    let dir = TestDir::temp();
+    let path = dir.path("stable.json").to_str().unwrap().to_string();
+    let (graph, remaining) = make_sample_stable_graph();
+
+    graph.serialize_graph_to_file(&path).unwrap();
+    let read: Box<StableGraph<Person, FriendOf>> =
+        GraphReadWriter::deserialize_graph_from_file(&path).unwrap();
+
+    assert_eq!(
+        QueryGraph::nodes(read.as_ref()).collect::<Vec<_>>(),
+        remaining
+    );
+    for n in remaining {
+        assert_eq!(
+            QueryGraph::node_weight(read.as_ref(), n),
+            QueryGraph::node_weight(&graph, n)
+        );
+    }
+}
+
+/// Printing skips removed nodes.
+#[test]
+fn test_stable_graph_print() {
+    let (graph, _) = make_sample_stable_graph();
+    let dot = graph.print();
+    assert!(!dot.contains("tobias"));
+    assert!(dot.contains("bettina"));
+}
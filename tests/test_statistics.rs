@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use graphfind_rs::algorithms::{summarize, GraphSummary};
+use petgraph::graph::UnGraph;
+
+pub mod common;
+use common::{make_sample_graph_variant, Person, Role};
+
+/// Classifies people by their role.
+fn role(person: &Person) -> &'static str {
+    match person.role {
+        Role::Professor { .. } => "Professor",
+        Role::Student { .. } => "Student",
+    }
+}
+
+/// Check the summary of a directed graph.
+#[test]
+fn test_summarize_directed() {
+    let graph = make_sample_graph_variant();
+    let summary = summarize(&graph, role, |f| f.since_year);
+
+    assert!(summary.directed);
+    assert_eq!(summary.node_count, 4);
+    assert_eq!(summary.edge_count, 4);
+    assert_eq!(summary.in_degrees, BTreeMap::from([(0, 2), (2, 2)]));
+    assert_eq!(summary.out_degrees, BTreeMap::from([(1, 4)]));
+    // Horst and Bettina are connected in both directions, which doesn't count as parallel edges.
+    assert_eq!(summary.parallel_edges, 0);
+    assert_eq!(summary.self_loops, 0);
+    assert_eq!(summary.components, 1);
+    assert_eq!(
+        summary.node_kinds,
+        BTreeMap::from([("Professor", 1), ("Student", 3)])
+    );
+    assert_eq!(
+        summary.edge_kinds,
+        BTreeMap::from([(2010, 2), (2018, 1), (2020, 1)])
+    );
+}
+
+/// Check the summary of an undirected graph with parallel edges, self loops and several components,
+/// and that it can be written to JSON and read back.
+#[test]
+fn test_summarize_undirected() {
+    let mut graph = UnGraph::new_undirected();
+    let a = graph.add_node('a');
+    let b = graph.add_node('b');
+    graph.add_node('c');
+    graph.add_edge(a, b, 1);
+    graph.add_edge(b, a, 2);
+    graph.add_edge(a, a, 3);
+
+    let summary = summarize(&graph, |_| "Letter".to_string(), |w| w % 2 == 0);
+    assert!(!summary.directed);
+    assert_eq!(summary.parallel_edges, 1);
+    assert_eq!(summary.self_loops, 1);
+    assert_eq!(summary.components, 2);
+    assert_eq!(summary.in_degrees, summary.out_degrees);
+    assert_eq!(summary.edge_kinds, BTreeMap::from([(false, 2), (true, 1)]));
+
+    let json = serde_json::to_string(&summary).unwrap();
+    let read: GraphSummary<String, bool> = serde_json::from_str(&json).unwrap();
+    assert_eq!(read, summary);
+}
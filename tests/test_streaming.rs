@@ -0,0 +1,184 @@
+use std::io::{BufReader, Read};
+
+use graphfind_rs::{
+    formats::{read_edge_list, read_ndjson, JsonGraph, LoadProgress, PROGRESS_INTERVAL},
+    graph::{Graph as RQLGraph, GraphIoError},
+    mixed_graph::MixedGraph,
+    pattern_matching::is_isomorphic,
+};
+use petgraph::graph::{DiGraph, UnGraph};
+
+pub mod common;
+use common::{make_sample_graph_variant, FriendOf, Person};
+
+/// Check reading the records of a JSON graph, one per line.
+#[test]
+fn test_read_ndjson() {
+    let graph = make_sample_graph_variant();
+    let json = JsonGraph::from_graph(&graph);
+    let mut text = String::new();
+    for node in &json.nodes {
+        text += &serde_json::to_string(node).unwrap();
+        text += "\n";
+    }
+    text += "\n";
+    for edge in &json.edges {
+        text += &serde_json::to_string(edge).unwrap();
+        text += "\n";
+    }
+
+    let mut reports = vec![];
+    let read: DiGraph<Person, FriendOf> =
+        read_ndjson(text.as_bytes(), |p: &LoadProgress| reports.push(*p)).unwrap();
+    assert!(is_isomorphic(&graph, &read, Person::eq, FriendOf::eq).is_some());
+    assert_eq!(
+        reports,
+        vec![LoadProgress {
+            bytes: text.len() as u64,
+            lines: 9,
+            nodes: 4,
+            edges: 4
+        }]
+    );
+}
+
+/// Check that edges whose direction differs from the graph are read into mixed graphs.
+#[test]
+fn test_read_ndjson_mixed() {
+    let text = "{\"id\":0,\"weight\":1}\n{\"id\":1,\"weight\":2}\n\
+        {\"source\":0,\"target\":1,\"weight\":3}\n\
+        {\"source\":1,\"target\":0,\"weight\":4,\"directed\":false}";
+    let read: MixedGraph<u8, u8> = read_ndjson(text.as_bytes(), |_: &_| {}).unwrap();
+    let directions: Vec<_> = read.edges().map(|e| read.is_directed_edge(e)).collect();
+    assert_eq!(directions, vec![true, false]);
+}
+
+/// Generates a large edge list line by line, without keeping it in memory.
+struct EdgeListSource {
+    line: usize,
+    lines: usize,
+    pending: Vec<u8>,
+}
+
+impl Read for EdgeListSource {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() && self.line < self.lines {
+            self.pending = match self.line {
+                0 => "# A generated edge list\n".to_string(),
+                i => format!("{}\t{} {}\n", i % 1000, i * 7 % 1000, i as f64 / 2.0),
+            }
+            .into_bytes();
+            self.line += 1;
+        }
+        let len = buffer.len().min(self.pending.len());
+        buffer[..len].copy_from_slice(&self.pending[..len]);
+        self.pending.drain(..len);
+        Ok(len)
+    }
+}
+
+/// Check reading a large edge list, with progress reports.
+#[test]
+fn test_read_edge_list() {
+    let lines = 3 * PROGRESS_INTERVAL as usize + 10;
+    let source = EdgeListSource {
+        line: 0,
+        lines,
+        pending: vec![],
+    };
+    let mut reports = vec![];
+    let graph: DiGraph<u32, f64> =
+        read_edge_list(BufReader::new(source), |p: &LoadProgress| reports.push(*p)).unwrap();
+
+    assert_eq!(graph.count_nodes(), 1000);
+    assert_eq!(graph.count_edges(), lines - 1);
+    assert_eq!(reports.len(), 4);
+    assert_eq!(reports[0].lines, PROGRESS_INTERVAL);
+    assert_eq!(reports[0].edges, PROGRESS_INTERVAL as usize - 1);
+    assert_eq!(reports[3].lines, lines as u64);
+    assert_eq!(reports[3].nodes, 1000);
+    assert!(reports.windows(2).all(|w| w[0].bytes < w[1].bytes));
+
+    let e = RQLGraph::edges(&graph).nth(2).unwrap();
+    let (a, b) = graph.adjacent_nodes(e);
+    assert_eq!(
+        (
+            RQLGraph::node_weight(&graph, a),
+            RQLGraph::node_weight(&graph, b)
+        ),
+        (&3, &21)
+    );
+    assert_eq!(RQLGraph::edge_weight(&graph, e), &1.5);
+}
+
+/// Check edge lists with named nodes, edges without weights and comments.
+#[test]
+fn test_read_edge_list_unweighted() {
+    let text = "% KONECT-style comment\nUlm   Neu-Ulm\n\nNeu-Ulm Senden  \n";
+    let graph: UnGraph<String, ()> = read_edge_list(text.as_bytes(), |_: &_| {}).unwrap();
+    let mut names: Vec<_> = graph.node_weights().collect();
+    names.sort();
+    assert_eq!(names, vec!["Neu-Ulm", "Senden", "Ulm"]);
+    assert_eq!(graph.count_edges(), 2);
+}
+
+/// Check that invalid lines are rejected, along with their line number.
+#[test]
+fn test_read_streaming_errors() {
+    let read_ndjson = |text: &str| {
+        read_ndjson::<DiGraph<u8, u8>, _, _, _, _>(text.as_bytes(), |_: &_| {}).unwrap_err()
+    };
+    let err = read_ndjson("{\"id\":0,\"weight\":1}\n{\"source\":0,\"target\":1,\"weight\":1}");
+    assert!(matches!(
+        &err,
+        GraphIoError::DanglingEdge { line: Some(2), node } if node == "1"
+    ));
+    let err = read_ndjson("\n  {\"id\":0,\"weight\":1\n");
+    assert!(
+        matches!(
+            err,
+            GraphIoError::Syntax {
+                line: 2,
+                column: Some(20),
+                ..
+            }
+        ),
+        "{err}"
+    );
+    for (text, line) in [
+        ("{\"id\":0,\"weight\":1}\n\n{\"id\":0,\"weight\":2}", 3),
+        ("{\"id\":0,\"weight\":1000}", 1),
+        (
+            "{\"id\":0,\"weight\":1}\n{\"source\":0,\"target\":0,\"weight\":1,\"directed\":false}",
+            2,
+        ),
+        ("{\"name\":0}", 1),
+    ] {
+        let err = read_ndjson(text);
+        assert!(matches!(err, GraphIoError::Schema { .. }), "{err}");
+        assert_eq!(err.line(), Some(line));
+        assert!(
+            err.to_string().starts_with(&format!("Line {line}:")),
+            "{err}"
+        );
+    }
+
+    let read_edge_list = |text: &str| {
+        read_edge_list::<DiGraph<u8, u8>, _, _, _, _>(text.as_bytes(), |_: &_| {}).unwrap_err()
+    };
+    assert!(matches!(
+        read_edge_list("1 2 3\n4\n"),
+        GraphIoError::Syntax {
+            line: 2,
+            column: None,
+            ..
+        }
+    ));
+    for text in ["1 2 x", "1 1000"] {
+        let err = read_edge_list(text);
+        assert!(
+            matches!(err, GraphIoError::Schema { line: Some(1), .. }),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use graphfind_rs::{
+    algorithms::{toposort, Bfs, DfsPostorder, DfsPreorder, Direction},
+    filter_map::FilterMap,
+    graph::Graph as QueryGraph,
+    matcher,
+    pattern_matching::{new_pattern, solve_vf, PatternGraph},
+};
+use petgraph::graph::{Graph, NodeIndex};
+
+/// Creates a small build dependency graph:
+/// `app` depends on `lib` and `log`, `lib` depends on `core` and `log`, `log` depends on `core`.
+fn make_dependency_graph() -> (Graph<&'static str, ()>, Vec<NodeIndex>) {
+    let mut graph = Graph::new();
+    let app = graph.add_node("app");
+    let lib = graph.add_node("lib");
+    let log = graph.add_node("log");
+    let core = graph.add_node("core");
+    graph.add_edge(app, lib, ());
+    graph.add_edge(app, log, ());
+    graph.add_edge(lib, core, ());
+    graph.add_edge(lib, log, ());
+    graph.add_edge(log, core, ());
+    (graph, vec![app, lib, log, core])
+}
+
+/// Returns the node weights of the given nodes.
+fn names<G: QueryGraph<&'static str, ()>>(
+    graph: &G,
+    nodes: impl Iterator<Item = G::NodeRef>,
+) -> Vec<&'static str> {
+    nodes.map(|n| *graph.node_weight(n)).collect()
+}
+
+/// Check the visiting order of the traversals in all directions.
+/// petgraph yields the edges of a node starting with the most recently added one.
+#[test]
+fn test_traversal_orders() {
+    let (graph, nodes) = make_dependency_graph();
+    let (app, log) = (nodes[0], nodes[2]);
+
+    let bfs = Bfs::new(&graph, app, Direction::Outgoing);
+    assert_eq!(names(&graph, bfs), vec!["app", "log", "lib", "core"]);
+    let preorder = DfsPreorder::new(&graph, app, Direction::Outgoing);
+    assert_eq!(names(&graph, preorder), vec!["app", "log", "core", "lib"]);
+    let postorder = DfsPostorder::new(&graph, app, Direction::Outgoing);
+    assert_eq!(names(&graph, postorder), vec!["core", "log", "lib", "app"]);
+
+    let dependents = Bfs::new(&graph, log, Direction::Incoming);
+    assert_eq!(names(&graph, dependents), vec!["log", "lib", "app"]);
+    let connected = DfsPreorder::new(&graph, log, Direction::Both);
+    assert_eq!(connected.count(), 4);
+    let dependencies = DfsPostorder::new(&graph, log, Direction::Outgoing);
+    assert_eq!(names(&graph, dependencies), vec!["core", "log"]);
+}
+
+/// Check that topological sorting orders dependencies and detects cycles.
+#[test]
+fn test_toposort() {
+    let (mut graph, nodes) = make_dependency_graph();
+
+    let order = toposort(&graph).unwrap();
+    assert_eq!(
+        names(&graph, order.into_iter()),
+        vec!["app", "lib", "log", "core"]
+    );
+
+    graph.add_edge(nodes[3], nodes[1], ());
+    let cycle = toposort(&graph).unwrap_err();
+    assert!([nodes[1], nodes[2], nodes[3]].contains(&cycle.node()));
+
+    let mut undirected = petgraph::graph::UnGraph::<(), ()>::new_undirected();
+    let a = undirected.add_node(());
+    assert_eq!(toposort(&undirected), Ok(vec![a]));
+    undirected.add_edge(a, a, ());
+    assert!(toposort(&undirected).is_err());
+}
+
+/// Traversals on filtered views only see the remaining elements.
+#[test]
+fn test_traversal_on_views() {
+    let (graph, nodes) = make_dependency_graph();
+    let app = nodes[0];
+
+    let without_lib = FilterMap::weight_filter(&graph, |n| *n != "lib", |_| true);
+    let bfs = Bfs::new(&without_lib, app, Direction::Outgoing);
+    let visited: Vec<_> = bfs.map(|n| **without_lib.node_weight(n)).collect();
+    assert_eq!(visited, vec!["app", "log", "core"]);
+
+    // Find dependency chains of length two, and traverse the matched subgraph.
+    let mut pattern = new_pattern();
+    let a = pattern.add_node(matcher!(&"app"));
+    let b = pattern.add_node(matcher!());
+    let c = pattern.add_node(matcher!());
+    pattern.add_edge(a, b, matcher!());
+    pattern.add_edge(b, c, matcher!());
+    let results = solve_vf(&pattern, &graph);
+    assert_eq!(results.len(), 3);
+    for result in &results {
+        assert_eq!(toposort(result).unwrap(), vec![a, b, c]);
+        assert_eq!(DfsPostorder::new(result, a, Direction::Outgoing).count(), 3);
+    }
+}